@@ -0,0 +1,60 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+
+use movement::{Classifier, Figure, Generator, Shape, Vector2F};
+use std::process;
+use std::time::{Duration, Instant};
+
+const POINTS: usize = 10_000;
+const ITERATIONS: u32 = 5;
+/// Longest classification of a stroke that still feels instant after the mouse stops.
+const BUDGET_MS: f64 = 100.0;
+
+fn stroke(figure: Figure, centre: Vector2F, size: f64, rotation: f64) -> Shape {
+    Generator { centre, size, rotation, samples: POINTS, ..Generator::default() }.stroke(figure).shape()
+}
+
+/// Average time of `function` in milliseconds.
+fn bench<T>(name: &str, mut function: impl FnMut() -> T) -> f64 {
+    let start: Instant = Instant::now();
+    for _ in 0..ITERATIONS {
+        std::hint::black_box(function());
+    }
+    let average: Duration = start.elapsed() / ITERATIONS;
+    println!("{name:<32} {:>10.3} ms", average.as_secs_f64() * 1000.0);
+    average.as_secs_f64() * 1000.0
+}
+
+fn main() {
+    let circle: Shape = stroke(Figure::Circle, Vector2F::new(2000.0, 1000.0), 800.0, 0.0);
+    let ellipse: Shape = stroke(Figure::Ellipse { aspect: 250.0 / 600.0 }, Vector2F::new(2000.0, 1000.0), 1200.0, 0.4);
+    let line: Shape = stroke(Figure::Line, Vector2F::new(1550.0, 550.0), Vector2F::new(2900.0, 700.0).abs(), 700f64.atan2(2900.0));
+    let classifier: Classifier = Classifier::default();
+    println!("{POINTS} points per stroke, average of {ITERATIONS} runs, budget {BUDGET_MS} ms per classification");
+    bench("get_distances (circle)", || circle.get_distances());
+    bench("get_distances (line)", || line.get_distances());
+    let mut over_budget: Vec<&str> = Vec::new();
+    for (name, shape) in [("classify (circle)", &circle), ("classify (ellipse)", &ellipse), ("classify (line)", &line)] {
+        if bench(name, || classifier.classify(shape)) > BUDGET_MS {
+            over_budget.push(name);
+        }
+    }
+    if !over_budget.is_empty() {
+        eprintln!("Over budget: {}", over_budget.join(", "));
+        process::exit(1);
+    }
+}
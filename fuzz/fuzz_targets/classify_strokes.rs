@@ -0,0 +1,37 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Feeds arbitrary recording files through the parser and every stroke in them through the classifier.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use movement::stroke_file::parse_strokes;
+use movement::{Classification, Classifier, Limits, Shape, ShapeName};
+
+fuzz_target!(|text: &str| {
+    let Ok(strokes) = parse_strokes(text) else { return };
+    let unlimited: Classifier = Classifier { limits: Limits { min_points: 0, min_size_px: 0.0, min_speed_px_per_s: 0.0, max_speed_px_per_s: f64::INFINITY }, ..Classifier::default() };
+    for coordinates in strokes {
+        let shape: Shape = Shape { coordinates, shape_type: ShapeName::Undefined };
+        let classifications: [Classification; 2] = [shape.classify(), unlimited.classify(&shape)];
+        for classification in classifications {
+            assert!((0.0..=100.0).contains(&classification.score), "{:?}", classification);
+        }
+        let probability: f64 = unlimited.rank(&shape).iter().map(|hypothesis| hypothesis.probability).sum();
+        assert!((probability - 1.0).abs() < 1e-9, "{}", probability);
+    }
+});
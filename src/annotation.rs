@@ -0,0 +1,175 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Gestures used to annotate documents: the check mark (✓), the single-stroke X or alpha loop,
+//! the zig-zag used to scratch something out and the caret (^).
+
+use crate::curvature::resample;
+use crate::segment::{Segment, SegmentKind};
+use crate::shape::{BoundingBox, Shape, ShapeName, Swipe};
+use crate::star::segment_intersection;
+use crate::vector::Vector2F;
+use crate::TOLERANCE_GENERAL;
+
+/// Number of points the stroke is resampled to before looking for the crossing of an X.
+const CROSSING_RESAMPLE_POINTS: usize = 128;
+/// Smallest angle between the two parts of an X where they cross.
+const CROSSING_ANGLE_DEG: f64 = 30.0;
+/// Smallest turn between two parts of a zig-zag; anything flatter is not drawn back and forth.
+const REVERSAL_ANGLE_DEG: f64 = 110.0;
+
+/// Swipe angles of the lines of `segments`, in degrees; `None` if any of them is an arc.
+fn line_angles(segments: &[Segment]) -> Option<Vec<f64>> {
+    segments.iter().map(|segment| match segment.kind {
+        SegmentKind::Line(swipe) => Some(swipe.angle.to_degrees()),
+        SegmentKind::Arc(_) => None,
+    }).collect()
+}
+
+fn swipe(segment: &Segment) -> Option<Swipe> {
+    match segment.kind {
+        SegmentKind::Line(swipe) => Some(swipe),
+        SegmentKind::Arc(_) => None,
+    }
+}
+
+impl Shape {
+    /// Recognizes annotation gestures, returning the shape and a score in percent.
+    pub fn annotation(&self) -> Option<(ShapeName, f64)> {
+        let segments: Vec<Segment> = self.segments();
+        if let Some(score) = self.check_mark(&segments) {
+            Some((ShapeName::Check, score))
+        } else if let Some(score) = self.caret(&segments) {
+            Some((ShapeName::Caret, score))
+        } else if let Some(score) = self.zig_zag(&segments) {
+            Some((ShapeName::ZigZag, score))
+        } else {
+            self.cross().map(|score| (ShapeName::Cross, score))
+        }
+    }
+
+    /// Mean line score of the parts of the stroke.
+    fn segment_score(&self, segments: &[Segment]) -> f64 {
+        let scores: f64 = segments.iter().map(|segment| Shape { coordinates: self.coordinates[segment.start..=segment.end].to_vec(), shape_type: ShapeName::Undefined }.line_score()).sum();
+        scores / segments.len() as f64
+    }
+
+    /// A short line down or down to the right, followed by a longer line up to the right.
+    fn check_mark(&self, segments: &[Segment]) -> Option<f64> {
+        let [down, up] = segments else {
+            return None;
+        };
+        let (down, up) = (swipe(down)?, swipe(up)?);
+        let angles: (f64, f64) = (down.angle.to_degrees(), up.angle.to_degrees());
+        if (-100.0..=-10.0).contains(&angles.0) && (20.0..=80.0).contains(&angles.1) && up.length >= (1.0 + TOLERANCE_GENERAL) * down.length {
+            Some(self.segment_score(segments))
+        } else {
+            None
+        }
+    }
+
+    /// A line up to the right followed by a line of similar length and steepness down to the right.
+    fn caret(&self, segments: &[Segment]) -> Option<f64> {
+        let [up, down] = segments else {
+            return None;
+        };
+        let (up, down) = (swipe(up)?, swipe(down)?);
+        // Angles of both legs against the vertical.
+        let legs: (f64, f64) = (90.0 - up.angle.to_degrees(), 90.0 + down.angle.to_degrees());
+        let ratio: f64 = up.length / down.length;
+        if (15.0..=75.0).contains(&legs.0) && (15.0..=75.0).contains(&legs.1) && (legs.0 - legs.1).abs() <= 30.0 && (0.5..=2.0).contains(&ratio) {
+            Some(self.segment_score(segments))
+        } else {
+            None
+        }
+    }
+
+    /// At least four lines, each drawn back roughly the way the one before came.
+    fn zig_zag(&self, segments: &[Segment]) -> Option<f64> {
+        let angles: Vec<f64> = line_angles(segments)?;
+        let reversing: bool = angles.windows(2).all(|pair| {
+            let turn: f64 = (pair[1] - pair[0]).rem_euclid(360.0);
+            turn.min(360.0 - turn) >= REVERSAL_ANGLE_DEG
+        });
+        if segments.len() >= 4 && reversing {
+            Some(self.segment_score(segments))
+        } else {
+            None
+        }
+    }
+
+    /// An open stroke that crosses itself exactly once, steeply and close to the middle of its
+    /// bounding box, like an X drawn without lifting the mouse or an alpha loop.
+    /// The score says how close to perpendicular the crossing is.
+    fn cross(&self) -> Option<f64> {
+        let size: f64 = self.get_distances().max;
+        let (first, last) = (self.coordinates.first()?.to_f64(), self.coordinates.last()?.to_f64());
+        if size == 0.0 || first.distance(last) < TOLERANCE_GENERAL * size {
+            return None;
+        }
+        let points: Vec<Vector2F> = resample(&self.coordinates, CROSSING_RESAMPLE_POINTS).into_iter().map(|(point, _)| point).collect();
+        let mut crossings: Vec<(Vector2F, f64)> = Vec::new();
+        for i in 0..points.len().saturating_sub(1) {
+            for j in i + 2..points.len() - 1 {
+                if let Some(point) = segment_intersection(points[i], points[i + 1], points[j], points[j + 1]) {
+                    crossings.push((point, (points[i + 1] - points[i]).angle_to(points[j + 1] - points[j]).sin().abs()));
+                }
+            }
+        }
+        let [(point, sine)] = crossings.as_slice() else {
+            return None;
+        };
+        let bounding_box: BoundingBox = self.bounding_box();
+        let relative: Vector2F = Vector2F::new((point.x - bounding_box.min.x) / bounding_box.width(), (point.y - bounding_box.min.y) / bounding_box.height());
+        let central = |value: f64| value.is_finite() && (TOLERANCE_GENERAL..=1.0 - TOLERANCE_GENERAL).contains(&value);
+        if central(relative.x) && central(relative.y) && *sine >= CROSSING_ANGLE_DEG.to_radians().sin() {
+            Some(sine * 100.0)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::fixtures::polyline;
+    use crate::shape::Classification;
+
+    #[test]
+    fn test_annotations() {
+        let gestures: [(&[(i32, i32)], ShapeName); 5] = [
+            (&[(100, 200), (160, 280), (320, 40)], ShapeName::Check),
+            (&[(100, 300), (200, 100), (300, 300)], ShapeName::Caret),
+            (&[(100, 100), (400, 120), (110, 150), (410, 180), (120, 210), (420, 240)], ShapeName::ZigZag),
+            (&[(100, 100), (300, 300), (300, 100), (100, 300)], ShapeName::Cross),
+            // Both legs of the same length are neither a check mark nor a caret.
+            (&[(100, 100), (200, 200), (300, 100)], ShapeName::Unknown),
+        ];
+        for (corners, shape_name) in gestures {
+            let classification: Classification = polyline(corners).classify();
+            assert_eq!(classification.shape_name, shape_name, "{:?}", corners);
+            if shape_name != ShapeName::Unknown {
+                let bounding_box: BoundingBox = classification.bounding_box.unwrap();
+                let (x, y) = (corners.iter().map(|corner| corner.0 as f64), corners.iter().map(|corner| corner.1 as f64));
+                assert_eq!(bounding_box.min, Vector2F::new(x.clone().fold(f64::MAX, f64::min), y.clone().fold(f64::MAX, f64::min)));
+                assert_eq!(bounding_box.max, Vector2F::new(x.fold(f64::MIN, f64::max), y.fold(f64::MIN, f64::max)));
+            }
+        }
+        // Neither is a vertical first leg.
+        assert_eq!(polyline(&[(100, 300), (100, 100), (300, 300)]).annotation(), None);
+    }
+}
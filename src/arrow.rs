@@ -0,0 +1,126 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::segment::{Segment, SegmentKind};
+use crate::shape::{Shape, ShapeName, Swipe};
+use crate::vector::Vector2F;
+use crate::{LINE_TOLERANCE_PX, TOLERANCE_GENERAL};
+
+/// Largest size of an arrow head, relative to the length of the shaft.
+const HEAD_MAX_RATIO: f64 = 0.6;
+/// Smallest distance of both barbs to the shaft, relative to the length of the shaft.
+const BARB_MIN_RATIO: f64 = 0.05;
+
+/// A shaft with a head at its tip.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Arrow {
+    pub tail: Vector2F,
+    pub tip: Vector2F,
+    /// Direction and length of the shaft, from the tail to the tip.
+    pub swipe: Swipe,
+}
+
+/// Whether `head` looks like the head of an arrow from `tail` to `tip`: all of its points are close
+/// to the tip, and it reaches back behind the tip on both sides of the shaft.
+fn is_head(tail: Vector2F, tip: Vector2F, head: &[Vector2F]) -> bool {
+    let length: f64 = tail.distance(tip);
+    let direction: Vector2F = (tip - tail).normalize();
+    if length == 0.0 || head.is_empty() || head.iter().any(|point| point.distance(tip) > HEAD_MAX_RATIO * length) {
+        return false;
+    }
+    let barb = |side: f64| head.iter().any(|&point| {
+        let relative: Vector2F = point - tip;
+        let offset: f64 = direction.cross(relative) * side;
+        relative.dot(direction) < 0.0 && offset >= LINE_TOLERANCE_PX.max(BARB_MIN_RATIO * length)
+    });
+    barb(1.0) && barb(-1.0)
+}
+
+fn arrow(tail: Vector2F, tip: Vector2F) -> Option<Arrow> {
+    Swipe::from_vector(tip - tail).map(|swipe| Arrow { tail, tip, swipe })
+}
+
+impl Shape {
+    /// Recognizes an arrow drawn in one stroke: a straight shaft from the tail to the tip,
+    /// followed by a head drawn around the tip.
+    pub fn arrow(&self) -> Option<Arrow> {
+        self.arrow_with_score().map(|(arrow, _)| arrow)
+    }
+
+    /// The arrow and how straight its shaft is, in percent.
+    pub(crate) fn arrow_with_score(&self) -> Option<(Arrow, f64)> {
+        let segments: Vec<Segment> = self.segments();
+        let (shaft, rest) = segments.split_first()?;
+        if rest.is_empty() || !matches!(shaft.kind, SegmentKind::Line(_)) {
+            return None;
+        }
+        let shaft: Shape = Shape { coordinates: self.coordinates[shaft.start..=shaft.end].to_vec(), shape_type: ShapeName::Undefined };
+        let (tail, tip) = (shaft.coordinates[0].to_f64(), shaft.coordinates[shaft.coordinates.len() - 1].to_f64());
+        let head: Vec<Vector2F> = self.coordinates[segments[0].end..].iter().map(|coordinate| coordinate.to_f64()).collect();
+        let score: f64 = shaft.line_score();
+        if score < 100.0 - 100.0 * TOLERANCE_GENERAL || !is_head(tail, tip, &head) {
+            return None;
+        }
+        arrow(tail, tip).map(|arrow| (arrow, score))
+    }
+
+    /// Recognizes an arrow whose head was drawn as a separate stroke. `self` is the shaft and has to
+    /// be a line; the tip is the end of the shaft the head was drawn at.
+    pub fn arrow_with_head(&self, head: &Shape) -> Option<Arrow> {
+        let (first, last) = (self.coordinates.first()?.to_f64(), self.coordinates.last()?.to_f64());
+        if self.line_score() < 100.0 - 100.0 * TOLERANCE_GENERAL {
+            return None;
+        }
+        let head: Vec<Vector2F> = head.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+        if is_head(first, last, &head) {
+            arrow(first, last)
+        } else if is_head(last, first, &head) {
+            arrow(last, first)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction;
+    use crate::shape::fixtures::polyline;
+    use crate::shape::Classification;
+
+    #[test]
+    fn test_arrows() {
+        let arrow: Shape = polyline(&[(100, 300), (500, 300), (440, 260), (500, 300), (440, 340)]);
+        let classification: Classification = arrow.classify();
+        assert_eq!(classification.shape_name, ShapeName::Arrow);
+        assert_eq!(classification.to_string(), "ARROW (100%, right, 400 px)");
+        let found: Arrow = classification.arrow.unwrap();
+        assert_eq!((found.tail, found.tip), (Vector2F::new(100.0, 300.0), Vector2F::new(500.0, 300.0)));
+        // A triangular head, pointing up.
+        assert_eq!(polyline(&[(300, 500), (300, 100), (330, 150), (270, 150), (300, 100)]).arrow().map(|arrow| arrow.swipe.direction), Some(Direction::Up));
+        // A hook on one side only is not a head.
+        assert_eq!(polyline(&[(100, 300), (500, 300), (440, 260)]).arrow(), None);
+
+        let shaft: Shape = polyline(&[(500, 500), (200, 200)]);
+        let head: Shape = polyline(&[(200, 260), (200, 200), (260, 200)]);
+        let found: Arrow = shaft.arrow_with_head(&head).unwrap();
+        assert_eq!((found.tip, found.swipe.direction), (Vector2F::new(200.0, 200.0), Direction::UpLeft));
+        let head: Shape = polyline(&[(440, 500), (500, 500), (500, 440)]);
+        assert_eq!(shaft.arrow_with_head(&head).map(|arrow| arrow.tip), Some(Vector2F::new(500.0, 500.0)));
+        assert_eq!(shaft.arrow_with_head(&polyline(&[(100, 100), (150, 100)])), None);
+    }
+}
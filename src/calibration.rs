@@ -0,0 +1,103 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Turning the scores of different recognizers into probabilities that can be compared.
+//!
+//! Every recognizer measures its score in its own way, so 80% for a circle and 80% for an ellipse
+//! do not mean the same. A [`Calibration`] maps a score to the probability that the stroke really is
+//! the shape, using a logistic curve (Platt scaling). It can be fitted on scores of labelled strokes.
+
+/// Number of steps of [`Calibration::fit`].
+const FIT_ITERATIONS: usize = 50;
+/// Keeps the fitted curve from becoming a step when the samples can be separated perfectly.
+const FIT_REGULARIZATION: f64 = 0.01;
+
+/// A logistic curve: the probability is 50% at `midpoint` and rises by `slope` per percent of score.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Calibration {
+    pub midpoint: f64,
+    pub slope: f64,
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+impl Calibration {
+    /// Probability between 0 and 1 for a score in percent; 0 for a score that is not a number.
+    pub fn probability(&self, score: f64) -> f64 {
+        let probability: f64 = sigmoid(self.slope * (score - self.midpoint));
+        if probability.is_nan() { 0.0 } else { probability }
+    }
+
+    /// The logarithm of the odds `probability / (1 - probability)`; minus infinity for a score that
+    /// is not a number.
+    pub fn log_odds(&self, score: f64) -> f64 {
+        let log_odds: f64 = self.slope * (score - self.midpoint);
+        if log_odds.is_nan() { f64::NEG_INFINITY } else { log_odds }
+    }
+
+    /// Fits the curve to scores of strokes that were (`true`) or were not (`false`) the shape,
+    /// by logistic regression. `None` if there are no samples or only one kind of them.
+    pub fn fit(samples: &[(f64, bool)]) -> Option<Self> {
+        let samples: Vec<(f64, bool)> = samples.iter().copied().filter(|(score, _)| score.is_finite()).collect();
+        if !samples.iter().any(|(_, positive)| *positive) || samples.iter().all(|(_, positive)| *positive) {
+            return None;
+        }
+        // Newton's method on scores between 0 and 1, which keeps the two parameters in proportion.
+        let (mut weight, mut bias) = (0.0, 0.0);
+        for _ in 0..FIT_ITERATIONS {
+            let (mut gradient, mut hessian) = ([FIT_REGULARIZATION * weight, 0.0], [[FIT_REGULARIZATION, 0.0], [0.0, 0.0]]);
+            for &(score, positive) in &samples {
+                let x: f64 = score / 100.0;
+                let predicted: f64 = sigmoid(weight * x + bias);
+                let (error, variance) = (predicted - if positive { 1.0 } else { 0.0 }, predicted * (1.0 - predicted));
+                gradient = [gradient[0] + error * x, gradient[1] + error];
+                hessian = [[hessian[0][0] + variance * x * x, hessian[0][1] + variance * x], [hessian[1][0] + variance * x, hessian[1][1] + variance]];
+            }
+            let determinant: f64 = hessian[0][0] * hessian[1][1] - hessian[0][1] * hessian[1][0];
+            if determinant.abs() < f64::EPSILON {
+                break;
+            }
+            weight -= (hessian[1][1] * gradient[0] - hessian[0][1] * gradient[1]) / determinant;
+            bias -= (hessian[0][0] * gradient[1] - hessian[1][0] * gradient[0]) / determinant;
+        }
+        if weight <= 0.0 {
+            return None;
+        }
+        Some(Calibration { midpoint: -bias / weight * 100.0, slope: weight / 100.0 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration() {
+        let calibration: Calibration = Calibration { midpoint: 75.0, slope: 0.2 };
+        assert_eq!(calibration.probability(75.0), 0.5);
+        assert!(calibration.probability(95.0) > 0.95 && calibration.probability(55.0) < 0.05);
+        assert_eq!(calibration.probability(f64::NAN), 0.0);
+        assert!((calibration.log_odds(85.0) - 2.0).abs() < 1e-12 && calibration.log_odds(f64::NAN) == f64::NEG_INFINITY);
+
+        let samples: Vec<(f64, bool)> = (0..=100).map(|score| (score as f64, score > 60 || score % 7 == 0 && score > 40)).collect();
+        let fitted: Calibration = Calibration::fit(&samples).unwrap();
+        assert!((fitted.midpoint - 58.0).abs() < 8.0, "{:?}", fitted);
+        assert!(fitted.probability(90.0) > 0.8 && fitted.probability(20.0) < 0.2);
+        assert_eq!(Calibration::fit(&[(80.0, true), (90.0, true)]), None);
+    }
+}
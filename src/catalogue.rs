@@ -0,0 +1,255 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Named closed shapes: the heart, the diamond, the teardrop, the crescent and the cloud.
+//!
+//! All of them are drawn in one stroke that winds around once, and they are told apart by their
+//! corners. A corner is convex if the stroke turns the same way there as it does overall, and
+//! concave if it turns back. Every detector returns a score in percent, mostly how mirror-symmetric
+//! the stroke is about the axis of the shape. To add a shape, add a detector to [`CATALOGUE`].
+
+use std::f64::consts::{FRAC_PI_3, PI, TAU};
+
+use crate::curvature::{resample, Corner};
+use crate::shape::{Shape, ShapeName};
+use crate::vector::Vector2F;
+use crate::TOLERANCE_GENERAL;
+
+/// Number of points the stroke is resampled to before measuring its symmetry.
+const SYMMETRY_RESAMPLE_POINTS: usize = 128;
+/// Smallest turn at the tip of a heart, a teardrop or a crescent.
+const TIP_ANGLE: f64 = FRAC_PI_3;
+/// Share of the stroke in percent on its fitted ellipse above which a corner is a kink of a shaky
+/// hand, not the tip of a teardrop or a crescent.
+const TIP_MAX_ELLIPSE_SCORE: f64 = 90.0;
+/// Largest angle between a corner of a diamond and the nearest of the four main directions, as seen from its centre.
+const DIAMOND_ANGLE_TOLERANCE: f64 = PI / 8.0;
+/// Smallest number of bumps of a cloud.
+const CLOUD_MIN_BUMPS: usize = 4;
+
+/// What the detectors know about a closed stroke.
+struct Outline<'a> {
+    shape: &'a Shape,
+    corners: Vec<Corner>,
+    /// Sign of the total turning: 1 if the stroke winds clockwise on screen, -1 otherwise.
+    sign: f64,
+    points: Vec<Vector2F>,
+    centre: Vector2F,
+    size: f64,
+}
+
+type Detector = fn(&Outline) -> Option<f64>;
+
+/// The closed shapes that are recognized, in the order they are tried.
+const CATALOGUE: [(ShapeName, Detector); 5] = [
+    (ShapeName::Heart, heart),
+    (ShapeName::Diamond, diamond),
+    (ShapeName::Teardrop, teardrop),
+    (ShapeName::Crescent, crescent),
+    (ShapeName::Cloud, cloud),
+];
+
+impl Outline<'_> {
+    fn convex(&self) -> Vec<Corner> {
+        self.corners.iter().copied().filter(|corner| corner.angle.signum() == self.sign).collect()
+    }
+
+    fn concave(&self) -> Vec<Corner> {
+        self.corners.iter().copied().filter(|corner| corner.angle.signum() != self.sign).collect()
+    }
+
+    /// The parts of the stroke between neighbouring corners; the last one wraps around the start.
+    fn parts(&self) -> Vec<Shape> {
+        let coordinates = &self.shape.coordinates;
+        (0..self.corners.len()).map(|i| {
+            let (start, end) = (self.corners[i].index, self.corners[(i + 1) % self.corners.len()].index);
+            let part = if start < end { coordinates[start..=end].to_vec() } else { [&coordinates[start..], &coordinates[..=end]].concat() };
+            Shape { coordinates: part, shape_type: ShapeName::Undefined }
+        }).collect()
+    }
+
+    /// Whether the stroke lies on an ellipse, so that its corners are no real tips.
+    fn is_ellipse(&self) -> bool {
+        self.shape.ellipse_score().is_some_and(|score| score > TIP_MAX_ELLIPSE_SCORE)
+    }
+
+    /// Whether `part` bulges outwards, like the sides of a convex shape.
+    fn is_convex(&self, part: &Shape) -> bool {
+        let Some(winding) = part.winding() else {
+            return false;
+        };
+        self.shape.winding() == Some(winding)
+    }
+
+    /// How well the stroke matches its mirror image about the line through `a` and `b`, in percent.
+    fn symmetry(&self, a: Vector2F, b: Vector2F) -> f64 {
+        let axis: Vector2F = (b - a).normalize();
+        if axis == Vector2F::default() {
+            return 0.0;
+        }
+        let distance: f64 = self.points.iter().map(|&point| {
+            let relative: Vector2F = point - a;
+            let mirrored: Vector2F = a + axis * (2.0 * relative.dot(axis)) - relative;
+            self.points.windows(2).map(|pair| distance_to_segment(mirrored, pair[0], pair[1])).fold(f64::MAX, f64::min)
+        }).sum::<f64>() / self.points.len() as f64;
+        ((1.0 - distance / (TOLERANCE_GENERAL * self.size)) * 100.0).max(0.0)
+    }
+}
+
+fn distance_to_segment(point: Vector2F, a: Vector2F, b: Vector2F) -> f64 {
+    let ab: Vector2F = b - a;
+    let length: f64 = ab.dot(ab);
+    let t: f64 = if length > 0.0 { ((point - a).dot(ab) / length).clamp(0.0, 1.0) } else { 0.0 };
+    point.distance(a.lerp(b, t))
+}
+
+/// A dip at the top between two lobes and a tip at the bottom, symmetric about the line between them.
+fn heart(outline: &Outline) -> Option<f64> {
+    let (concave, convex) = (outline.concave(), outline.convex());
+    let ([dip], [tip]) = (concave.as_slice(), convex.as_slice()) else {
+        return None;
+    };
+    if tip.angle.abs() < TIP_ANGLE || !outline.parts().iter().all(|part| outline.is_convex(part)) {
+        return None;
+    }
+    Some(outline.symmetry(dip.point.to_f64(), tip.point.to_f64()))
+}
+
+/// Four straight sides, with a corner at the top, the bottom, the left and the right.
+fn diamond(outline: &Outline) -> Option<f64> {
+    let corners: Vec<Corner> = outline.convex();
+    if corners.len() != 4 || outline.corners.len() != 4 || !outline.parts().iter().all(|part| part.line_score() >= 100.0 - 100.0 * TOLERANCE_GENERAL) {
+        return None;
+    }
+    let mut quadrants: Vec<usize> = Vec::new();
+    for corner in &corners {
+        let angle: f64 = (corner.point.to_f64() - outline.centre).angle().rem_euclid(TAU);
+        let quadrant: f64 = (angle / (PI / 2.0)).round();
+        if (angle - quadrant * PI / 2.0).abs() > DIAMOND_ANGLE_TOLERANCE {
+            return None;
+        }
+        quadrants.push(quadrant as usize % 4);
+    }
+    quadrants.sort_unstable();
+    quadrants.dedup();
+    if quadrants.len() != 4 {
+        return None;
+    }
+    // Corners follow each other along the stroke, so opposite corners are two apart.
+    let diagonals: [(Vector2F, Vector2F); 2] = [(corners[0].point.to_f64(), corners[2].point.to_f64()), (corners[1].point.to_f64(), corners[3].point.to_f64())];
+    Some(diagonals.iter().map(|&(a, b)| outline.symmetry(a, b)).sum::<f64>() / 2.0)
+}
+
+/// A single pointed tip, with a round end opposite of it.
+fn teardrop(outline: &Outline) -> Option<f64> {
+    let [tip] = outline.corners.as_slice() else {
+        return None;
+    };
+    if tip.angle.signum() != outline.sign || tip.angle.abs() < TIP_ANGLE || outline.is_ellipse() {
+        return None;
+    }
+    Some(outline.symmetry(tip.point.to_f64(), outline.centre))
+}
+
+/// Two pointed horns joined by an outer arc bulging outwards and an inner arc bulging inwards.
+fn crescent(outline: &Outline) -> Option<f64> {
+    let horns: Vec<Corner> = outline.convex();
+    let [first, second] = horns.as_slice() else {
+        return None;
+    };
+    if outline.corners.len() != 2 || first.angle.abs() < TIP_ANGLE || second.angle.abs() < TIP_ANGLE || outline.is_ellipse() {
+        return None;
+    }
+    let parts: Vec<Shape> = outline.parts();
+    let inner: Vec<&Shape> = parts.iter().filter(|part| !outline.is_convex(part)).collect();
+    if inner.len() != 1 || inner[0].line_score() >= 100.0 - 100.0 * TOLERANCE_GENERAL {
+        return None;
+    }
+    let (a, b) = (first.point.to_f64(), second.point.to_f64());
+    let middle: Vector2F = a.lerp(b, 0.5);
+    Some(outline.symmetry(middle, middle + (b - a).rotate(PI / 2.0)))
+}
+
+/// At least four bumps bulging outwards, separated by concave corners.
+/// The score says how evenly the corners lie on a circle.
+fn cloud(outline: &Outline) -> Option<f64> {
+    let dips: Vec<Corner> = outline.concave();
+    if dips.len() < CLOUD_MIN_BUMPS || dips.len() != outline.corners.len() || !outline.parts().iter().all(|part| outline.is_convex(part)) {
+        return None;
+    }
+    let radius: f64 = dips.iter().map(|dip| dip.point.to_f64().distance(outline.centre)).sum::<f64>() / dips.len() as f64;
+    let deviation: f64 = dips.iter().map(|dip| (dip.point.to_f64().distance(outline.centre) - radius).abs()).sum::<f64>() / dips.len() as f64;
+    Some(if radius > 0.0 { ((1.0 - deviation / radius) * 100.0).max(0.0) } else { 0.0 })
+}
+
+impl Shape {
+    /// Recognizes the shapes of the catalogue, returning the first that matches and its score in percent.
+    pub fn closed_shape(&self) -> Option<(ShapeName, f64)> {
+        let (corners, total_turning) = self.closed_corners()?;
+        if (total_turning.abs() / TAU).round() != 1.0 {
+            return None;
+        }
+        let points: Vec<Vector2F> = resample(&self.coordinates, SYMMETRY_RESAMPLE_POINTS).into_iter().map(|(point, _)| point).collect();
+        let centre: Vector2F = points.iter().fold(Vector2F::default(), |sum, point| sum + *point) / points.len() as f64;
+        let outline: Outline = Outline { shape: self, corners, sign: total_turning.signum(), points, centre, size: self.get_distances().max };
+        CATALOGUE.iter().find_map(|(shape_name, detector)| detector(&outline).map(|score| (shape_name.clone(), score)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::fixtures::{cloud_curve, crescent_curve, heart_curve, polygon, teardrop_curve};
+    use crate::shape::Classification;
+    use crate::vector::Vector2D;
+
+    /// `count` points of `curve` for parameters from 0 to 1, scaled by `scale`, rotated by `rotation`
+    /// and starting `offset` of the way along the curve.
+    fn sample(curve: impl Fn(f64) -> Vector2F, count: usize, scale: f64, rotation: f64, offset: f64) -> Shape {
+        let coordinates: Vec<Vector2D> = (0..=count).map(|i| (Vector2F::new(600.0, 500.0) + (curve((i as f64 / count as f64 + offset) % 1.0) * scale).rotate(rotation)).round()).collect();
+        Shape { coordinates, shape_type: ShapeName::Undefined }
+    }
+
+    #[test]
+    fn test_closed_shapes() {
+        let rhombus: [(f64, f64); 4] = [(0.0, -1.0), (0.6, 0.0), (0.0, 1.0), (-0.6, 0.0)];
+        let dataset: Vec<(Shape, ShapeName)> = vec![
+            (sample(heart_curve, 120, 200.0, 0.0, 0.0), ShapeName::Heart),
+            (sample(heart_curve, 80, 120.0, 0.1, 0.3), ShapeName::Heart),
+            (sample(|t| heart_curve(1.0 - t), 150, 300.0, -0.1, 0.6), ShapeName::Heart),
+            (sample(polygon(&rhombus), 160, 200.0, 0.0, 0.0), ShapeName::Diamond),
+            (sample(polygon(&rhombus), 100, 150.0, 0.2, 0.1), ShapeName::Diamond),
+            (sample(polygon(&[(0.0, -1.0), (1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)]), 120, 250.0, 0.0, 0.4), ShapeName::Diamond),
+            (sample(teardrop_curve, 120, 200.0, 0.0, 0.0), ShapeName::Teardrop),
+            (sample(teardrop_curve, 90, 150.0, 2.0, 0.5), ShapeName::Teardrop),
+            (sample(|t| teardrop_curve(1.0 - t), 150, 300.0, -1.2, 0.25), ShapeName::Teardrop),
+            (sample(crescent_curve, 120, 200.0, 0.0, 0.0), ShapeName::Crescent),
+            (sample(crescent_curve, 100, 150.0, 1.5, 0.3), ShapeName::Crescent),
+            (sample(|t| crescent_curve(1.0 - t), 150, 250.0, 3.0, 0.7), ShapeName::Crescent),
+            (sample(cloud_curve(6), 180, 200.0, 0.0, 0.0), ShapeName::Cloud),
+            (sample(cloud_curve(5), 150, 150.0, 0.5, 0.1), ShapeName::Cloud),
+            (sample(|t| cloud_curve(7)(1.0 - t), 210, 250.0, 0.0, 0.5), ShapeName::Cloud),
+        ];
+        for (shape, shape_name) in dataset {
+            let classification: Classification = shape.classify();
+            assert_eq!(classification.shape_name, shape_name, "{} {:?}", classification, shape.closed_shape());
+            assert!(classification.score >= 90.0, "{}", classification);
+        }
+        // A square standing on one of its sides is no diamond, and a circle has no corners at all.
+        assert_eq!(sample(polygon(&[(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]), 160, 150.0, 0.0, 0.1).closed_shape(), None);
+        assert_eq!(sample(|t| Vector2F::new(1.0, 0.0).rotate(t * TAU), 100, 200.0, 0.0, 0.0).closed_shape(), None);
+    }
+}
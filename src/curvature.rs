@@ -0,0 +1,281 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use crate::shape::Shape;
+use crate::vector::{Vector2D, Vector2F};
+use crate::{CORNER_ANGLE_DEG, TOLERANCE_GENERAL};
+
+/// Number of evenly spaced points the stroke is resampled to before looking for corners.
+pub(crate) const RESAMPLE_POINTS: usize = 64;
+/// Distance in resampled points between a point and the neighbours its turning angle is measured to.
+pub(crate) const CORNER_WINDOW: usize = 2;
+
+/// Turning angles and discrete curvature at every point of a stroke.
+/// Angles are in radians and positive where the stroke turns clockwise on screen.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Curvature {
+    /// Angle between the step into a point and the step out of it; 0 at both ends
+    /// and wherever the cursor did not move.
+    pub turning_angles: Vec<f64>,
+    /// Turning angle divided by the mean length of the two steps, in radians per pixel.
+    pub curvature: Vec<f64>,
+    /// Sum of all turning angles: about ±2π for a closed loop, about 0 for a line or a zig-zag.
+    pub total_turning: f64,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Corner {
+    /// Index into `Shape.coordinates`.
+    pub index: usize,
+    pub point: Vector2D,
+    /// Turning angle at the corner, measured over a few pixels on both sides.
+    pub angle: f64,
+}
+
+/// Points spaced evenly along the stroke, each with the index of the closest original point.
+pub(crate) fn resample(coordinates: &[Vector2D], count: usize) -> Vec<(Vector2F, usize)> {
+    let points: Vec<Vector2F> = coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+    resample_points(&points, count)
+}
+
+pub(crate) fn resample_points(points: &[Vector2F], count: usize) -> Vec<(Vector2F, usize)> {
+    let length: f64 = points.windows(2).map(|pair| pair[0].distance(pair[1])).sum();
+    if points.len() < 2 || length == 0.0 {
+        return points.first().map(|&point| (point, 0)).into_iter().collect();
+    }
+    let step: f64 = length / (count - 1) as f64;
+    let mut resampled: Vec<(Vector2F, usize)> = vec![(points[0], 0)];
+    let mut travelled: f64 = 0.0;
+    let mut target: f64 = step;
+    for i in 0..points.len() - 1 {
+        let segment_length: f64 = points[i].distance(points[i + 1]);
+        while segment_length > 0.0 && target <= travelled + segment_length && resampled.len() < count - 1 {
+            let t: f64 = (target - travelled) / segment_length;
+            resampled.push((points[i].lerp(points[i + 1], t), if t < 0.5 { i } else { i + 1 }));
+            target += step;
+        }
+        travelled += segment_length;
+    }
+    resampled.push((points[points.len() - 1], points.len() - 1));
+    resampled
+}
+
+impl Shape {
+    /// Turning angle and curvature at every point. Repeated points are skipped, so a pause
+    /// of the cursor does not show up as a turn.
+    pub fn curvature(&self) -> Curvature {
+        let points: Vec<Vector2F> = self.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+        let mut turning_angles: Vec<f64> = vec![0.0; points.len()];
+        let mut curvature: Vec<f64> = vec![0.0; points.len()];
+        let mut previous: Option<usize> = None;
+        for i in 0..points.len() {
+            if previous.is_some_and(|previous| points[previous] == points[i]) {
+                continue;
+            }
+            let next: Option<usize> = (i + 1..points.len()).find(|&next| points[next] != points[i]);
+            if let (Some(before), Some(after)) = (previous, next) {
+                let (incoming, outgoing) = (points[i] - points[before], points[after] - points[i]);
+                turning_angles[i] = incoming.angle_to(outgoing);
+                curvature[i] = turning_angles[i] / ((incoming.abs() + outgoing.abs()) / 2.0);
+            }
+            previous = Some(i);
+        }
+        let total_turning: f64 = turning_angles.iter().sum();
+        Curvature { turning_angles, curvature, total_turning }
+    }
+
+    /// Points where the stroke turns by at least `CORNER_ANGLE_DEG`. The turning angle is measured
+    /// on an evenly resampled copy of the stroke, so that it does not depend on the drawing speed;
+    /// of several neighbouring candidates the sharpest is kept.
+    pub fn corners(&self) -> Vec<Corner> {
+        let points: Vec<(Vector2F, usize)> = resample(&self.coordinates, RESAMPLE_POINTS);
+        let mut corners: Vec<Corner> = Vec::new();
+        let mut sharpest: Option<(f64, Corner)> = None;
+        for i in CORNER_WINDOW..points.len().saturating_sub(CORNER_WINDOW) {
+            let before: Vector2F = points[i].0 - points[i - CORNER_WINDOW].0;
+            let after: Vector2F = points[i + CORNER_WINDOW].0 - points[i].0;
+            let angle: f64 = before.angle_to(after);
+            if angle.abs() >= CORNER_ANGLE_DEG.to_radians() {
+                if sharpest.is_none_or(|(sharpest, _)| angle.abs() > sharpest) {
+                    let (a, b) = (points[i - CORNER_WINDOW].0, points[i + CORNER_WINDOW].0);
+                    let index: usize = self.farthest_from_chord(points[i - 1].1..=points[i + 1].1, a, b);
+                    let point: Vector2F = self.coordinates[index].to_f64();
+                    sharpest = Some((angle.abs(), Corner { index, point: self.coordinates[index], angle: (point - a).angle_to(b - point) }));
+                }
+            } else if let Some((_, corner)) = sharpest.take() {
+                corners.push(corner);
+            }
+        }
+        corners.extend(sharpest.map(|(_, corner)| corner));
+        corners.dedup_by_key(|corner| corner.index);
+        corners.retain(|corner| corner.index != 0 && corner.index + 1 != self.coordinates.len());
+        corners
+    }
+
+    /// Like [`Shape::corners`], but also counts the places where the cursor slowed down to less
+    /// than `TOLERANCE_GENERAL` of its average speed and changed its direction by at least half of
+    /// `CORNER_ANGLE_DEG` while doing so, as people tend to do at rounded corners.
+    /// `timestamps` are the times of the coordinates since the start of the stroke;
+    /// if there is not one per coordinate, only the angles are used.
+    pub fn corners_with_timestamps(&self, timestamps: &[Duration]) -> Vec<Corner> {
+        let mut corners: Vec<Corner> = self.corners();
+        let length: usize = self.coordinates.len();
+        if timestamps.len() != length || length < 3 {
+            return corners;
+        }
+        let mut speeds: Vec<f64> = vec![f64::MAX; length];
+        for i in 1..length - 1 {
+            let time: f64 = timestamps[i + 1].saturating_sub(timestamps[i - 1]).as_secs_f64();
+            if time > 0.0 {
+                speeds[i] = self.coordinates[i - 1].to_f64().distance(self.coordinates[i + 1].to_f64()) / time;
+            }
+        }
+        let measured: Vec<f64> = speeds.iter().copied().filter(|speed| *speed < f64::MAX).collect();
+        if measured.is_empty() {
+            return corners;
+        }
+        let slow: f64 = TOLERANCE_GENERAL * measured.iter().sum::<f64>() / measured.len() as f64;
+        let travelled: Vec<f64> = self.travelled();
+        let reach: f64 = travelled[length - 1] * CORNER_WINDOW as f64 / (RESAMPLE_POINTS - 1) as f64;
+        let mut i: usize = 1;
+        while i < length - 1 {
+            if speeds[i] >= slow {
+                i += 1;
+                continue;
+            }
+            let start: usize = i;
+            while i < length - 1 && speeds[i] < slow {
+                i += 1;
+            }
+            let end: usize = i - 1;
+            let distance = |a: usize, b: usize| (travelled[a] - travelled[b]).abs();
+            if corners.iter().any(|corner| (start..=end).contains(&corner.index) || distance(corner.index, start).min(distance(corner.index, end)) < reach) {
+                continue;
+            }
+            // The last coordinate at least `reach` before the slow part, and the first one after it.
+            let before: usize = travelled[..start].partition_point(|&length| length <= travelled[start] - reach).saturating_sub(1);
+            let after: usize = (end + 1 + travelled[end + 1..].partition_point(|&length| length < travelled[end] + reach)).min(length - 1);
+            let angle: f64 = (self.coordinates[start].to_f64() - self.coordinates[before].to_f64()).angle_to(self.coordinates[after].to_f64() - self.coordinates[end].to_f64());
+            if angle.abs() >= CORNER_ANGLE_DEG.to_radians() / 2.0 {
+                let index: usize = (start..=end).min_by(|&a, &b| speeds[a].total_cmp(&speeds[b])).unwrap_or(start);
+                corners.push(Corner { index, point: self.coordinates[index], angle });
+            }
+        }
+        corners.sort_by_key(|corner| corner.index);
+        corners
+    }
+
+    /// Corners of a closed stroke, including the one where it starts and ends if there is one, and
+    /// its total turning: about ±2π times the number of times it winds around its centre.
+    /// `None` if the ends are further apart than `TOLERANCE_GENERAL` of the size of the stroke.
+    pub(crate) fn closed_corners(&self) -> Option<(Vec<Corner>, f64)> {
+        let size: f64 = self.get_distances().max;
+        let (first, last) = (self.coordinates.first()?.to_f64(), self.coordinates.last()?.to_f64());
+        if size == 0.0 || first.distance(last) > TOLERANCE_GENERAL * size {
+            return None;
+        }
+        let points: Vec<Vector2F> = resample(&self.coordinates, RESAMPLE_POINTS).into_iter().map(|(point, _)| point).collect();
+        if points.len() <= 2 * CORNER_WINDOW {
+            return None;
+        }
+        let mut corners: Vec<Corner> = self.corners();
+        let closing: f64 = (points[points.len() - 1] - points[points.len() - 1 - CORNER_WINDOW]).angle_to(points[CORNER_WINDOW] - points[0]);
+        if closing.abs() >= CORNER_ANGLE_DEG.to_radians() {
+            corners.insert(0, Corner { index: 0, point: self.coordinates[0], angle: closing });
+        }
+        let edges: Vec<Vector2F> = (0..points.len() - 1).map(|i| points[i + 1] - points[i]).chain([points[0] - points[points.len() - 1]]).filter(|edge| *edge != Vector2F::default()).collect();
+        let total_turning: f64 = (0..edges.len()).map(|i| edges[i].angle_to(edges[(i + 1) % edges.len()])).sum();
+        Some((corners, total_turning))
+    }
+
+    /// Length of the stroke from its start to every coordinate.
+    fn travelled(&self) -> Vec<f64> {
+        let mut travelled: Vec<f64> = Vec::with_capacity(self.coordinates.len());
+        let mut length: f64 = 0.0;
+        for (i, coordinate) in self.coordinates.iter().enumerate() {
+            if i > 0 {
+                length += self.coordinates[i - 1].to_f64().distance(coordinate.to_f64());
+            }
+            travelled.push(length);
+        }
+        travelled
+    }
+
+    /// The original point in `range` farthest from the line through `a` and `b`, which is where
+    /// the corner found on the resampled stroke actually is.
+    fn farthest_from_chord(&self, range: RangeInclusive<usize>, a: Vector2F, b: Vector2F) -> usize {
+        let mut farthest: (f64, usize) = (-1.0, *range.start());
+        for i in range {
+            let distance: f64 = self.coordinates[i].to_f64().distance_to_line(a, b).abs();
+            if distance > farthest.0 {
+                farthest = (distance, i);
+            }
+        }
+        farthest.1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::ShapeName;
+    use std::f64::consts::{PI, TAU};
+
+    #[test]
+    fn test_corners_and_turning() {
+        let mut coordinates: Vec<Vector2D> = Vec::new();
+        for (a, b) in [((100.0, 100.0), (300.0, 100.0)), ((300.0, 100.0), (300.0, 300.0)), ((300.0, 300.0), (100.0, 100.0))] {
+            for i in 0..40 {
+                coordinates.push(Vector2F::new(a.0, a.1).lerp(Vector2F::new(b.0, b.1), i as f64 / 40.0).round());
+            }
+        }
+        coordinates.push(Vector2D { x: 100, y: 100 });
+        let triangle: Shape = Shape { coordinates, shape_type: ShapeName::Undefined };
+        let corners: Vec<Corner> = triangle.corners();
+        assert_eq!(corners.iter().map(|corner| corner.index).collect::<Vec<usize>>(), vec![40, 80]);
+        assert_eq!(corners[0].point, Vector2D { x: 300, y: 100 });
+        assert!((corners[0].angle - PI / 2.0).abs() < 0.1 && (corners[1].angle - 3.0 * PI / 4.0).abs() < 0.1);
+        let curvature: Curvature = triangle.curvature();
+        assert!((curvature.turning_angles[40] - PI / 2.0).abs() < 1e-9);
+        assert!((curvature.total_turning - (PI / 2.0 + 3.0 * PI / 4.0)).abs() < 1e-9);
+
+        let circle: Shape = Shape { coordinates: (0..=100).map(|i| (Vector2F::new(0.0, -1.0).rotate(TAU * i as f64 / 100.0) * 200.0).round()).collect(), shape_type: ShapeName::Undefined };
+        assert!(circle.corners().is_empty());
+        assert!((circle.curvature().total_turning - TAU).abs() < 0.1);
+        assert!((circle.curvature().curvature[1..100].iter().sum::<f64>() / 99.0 - 1.0 / 200.0).abs() < 1e-4);
+
+        // A rounded turn drawn slowly is a corner only when the timestamps are known.
+        let mut coordinates: Vec<Vector2D> = (0..50).map(|i| Vector2D { x: 4 * i, y: 0 }).collect();
+        let arc_start: usize = coordinates.len();
+        coordinates.extend((1..10).map(|i| (Vector2F::new(200.0, 60.0) + Vector2F::new(0.0, -60.0).rotate(PI / 2.0 * i as f64 / 10.0)).round()));
+        coordinates.extend((0..50).map(|i| Vector2D { x: 260, y: 60 + 4 * i }));
+        let mut timestamps: Vec<Duration> = Vec::new();
+        let mut time: Duration = Duration::ZERO;
+        for i in 0..coordinates.len() {
+            time += Duration::from_millis(if (arc_start..arc_start + 9).contains(&i) { 200 } else { 10 });
+            timestamps.push(time);
+        }
+        let turn: Shape = Shape { coordinates, shape_type: ShapeName::Undefined };
+        assert!(turn.corners().is_empty());
+        let corners: Vec<Corner> = turn.corners_with_timestamps(&timestamps);
+        assert_eq!(corners.len(), 1);
+        assert!((arc_start..arc_start + 9).contains(&corners[0].index));
+        assert!(turn.corners_with_timestamps(&timestamps[1..]).is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::f64::consts::PI;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::vector::Vector2F;
+
+/// Compass direction on screen; `Up` points to the top of the screen.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+    Up,
+    UpRight,
+}
+
+const DIRECTIONS: [Direction; 8] = [Direction::Right, Direction::DownRight, Direction::Down, Direction::DownLeft, Direction::Left, Direction::UpLeft, Direction::Up, Direction::UpRight];
+const NAMES: [&str; 8] = ["right", "down-right", "down", "down-left", "left", "up-left", "up", "up-right"];
+const ABBREVIATIONS: [&str; 8] = ["R", "DR", "D", "DL", "L", "UL", "U", "UR"];
+const ARROWS: [&str; 8] = ["→", "↘", "↓", "↙", "←", "↖", "↑", "↗"];
+
+impl Direction {
+    /// Nearest of the 8 compass directions for a vector in screen coordinates (y pointing down).
+    pub fn from_vector(vector: Vector2F) -> Self {
+        let sector: f64 = (vector.angle() / (PI / 4.0)).round();
+        DIRECTIONS[sector.rem_euclid(8.0) as usize]
+    }
+
+    /// Nearest of the 4 main directions (right, down, left, up).
+    pub fn from_vector_4(vector: Vector2F) -> Self {
+        let sector: f64 = (vector.angle() / (PI / 2.0)).round();
+        DIRECTIONS[2 * sector.rem_euclid(4.0) as usize]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !matches!(self, Direction::Right | Direction::Down | Direction::Left | Direction::Up)
+    }
+
+    /// Short name as used in gesture patterns, such as `R` or `DL`.
+    pub fn abbreviation(self) -> &'static str {
+        ABBREVIATIONS[self.index()]
+    }
+
+    fn index(self) -> usize {
+        DIRECTIONS.iter().position(|direction| *direction == self).unwrap_or(0)
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(NAMES[self.index()])
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    /// Accepts the names, their abbreviations (`R`, `DL`, ...) and arrows (`→`, `↙`, ...).
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        NAMES.iter().chain(&ABBREVIATIONS).chain(&ARROWS).position(|known| known.eq_ignore_ascii_case(name)).map(|index| DIRECTIONS[index % 8]).ok_or_else(|| format!("unknown direction `{}`", name))
+    }
+}
+
+/// Turning direction of a closed shape as seen on screen.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+impl fmt::Display for Winding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Winding::Clockwise => "clockwise",
+            Winding::CounterClockwise => "counter-clockwise",
+        })
+    }
+}
+
+impl FromStr for Winding {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "clockwise" | "cw" | "↻" => Ok(Winding::Clockwise),
+            "counter-clockwise" | "counterclockwise" | "ccw" | "↺" => Ok(Winding::CounterClockwise),
+            _ => Err(format!("unknown winding `{}`", name)),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeSet;
+
+use crate::vector::Vector2D;
+
+/// Squared distance; exact for all `i32` coordinates, which is why it needs 128 bits.
+pub fn squared_distance(a: Vector2D, b: Vector2D) -> i128 {
+    let (dx, dy) = (a.x as i128 - b.x as i128, a.y as i128 - b.y as i128);
+    dx * dx + dy * dy
+}
+
+fn area(a: Vector2D, b: Vector2D, c: Vector2D) -> i128 {
+    (b.x as i128 - a.x as i128) * (c.y as i128 - a.y as i128) - (b.y as i128 - a.y as i128) * (c.x as i128 - a.x as i128)
+}
+
+/// Keeps `candidate` if it is farther (or closer, for `closest`) than `best`.
+/// Ties go to the pair with the lowest indices, so results do not depend on the search order.
+fn keep_pair(best: &mut Option<(i128, usize, usize)>, distance: i128, a: usize, b: usize, closest: bool) {
+    let candidate: (i128, usize, usize) = (distance, a.min(b), a.max(b));
+    let better: bool = match best {
+        None => true,
+        Some(current) if closest => candidate < *current,
+        Some(current) => (candidate.0, usize::MAX - candidate.1, usize::MAX - candidate.2) > (current.0, usize::MAX - current.1, usize::MAX - current.2),
+    };
+    if better {
+        *best = Some(candidate);
+    }
+}
+
+/// The two points of `points` that are farthest apart, as indices with the lower one first.
+/// `hull` must be the convex hull of `points` as returned by `Shape::convex_hull`;
+/// the antipodal pairs of its vertices are visited with rotating calipers.
+pub fn farthest_pair(points: &[Vector2D], hull: &[usize]) -> Option<(usize, usize)> {
+    let mut best: Option<(i128, usize, usize)> = None;
+    match hull.len() {
+        0 | 1 => return None,
+        2 => return Some((hull[0].min(hull[1]), hull[0].max(hull[1]))),
+        _ => (),
+    }
+    let length: usize = hull.len();
+    let vertex = |i: usize| points[hull[i % length]];
+    let mut j: usize = 1;
+    for i in 0..length {
+        while area(vertex(i), vertex(i + 1), vertex(j + 1)) > area(vertex(i), vertex(i + 1), vertex(j)) {
+            j += 1;
+        }
+        for (a, b) in [(i, j), (i + 1, j)] {
+            keep_pair(&mut best, squared_distance(vertex(a), vertex(b)), hull[a % length], hull[b % length], false);
+        }
+        if area(vertex(i), vertex(i + 1), vertex(j + 1)) == area(vertex(i), vertex(i + 1), vertex(j)) {
+            for (a, b) in [(i, j + 1), (i + 1, j + 1)] {
+                keep_pair(&mut best, squared_distance(vertex(a), vertex(b)), hull[a % length], hull[b % length], false);
+            }
+        }
+    }
+    best.map(|(_, a, b)| (a, b))
+}
+
+/// The two points of `points` at different positions that are closest to each other,
+/// as indices with the lower one first. Uses a sweep line over the points sorted by x,
+/// with the points inside the current strip kept in a tree ordered by y.
+pub fn closest_pair(points: &[Vector2D]) -> Option<(usize, usize)> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by_key(|&i| (points[i].x, points[i].y, i));
+    order.dedup_by_key(|i| points[*i]);
+    let mut best: Option<(i128, usize, usize)> = None;
+    let mut strip: BTreeSet<(i32, i32, usize)> = BTreeSet::new();
+    let mut left: usize = 0;
+    for &i in &order {
+        let point: Vector2D = points[i];
+        let width: i64 = best.map_or(i64::MAX, |(distance, _, _)| (distance as f64).sqrt().ceil() as i64);
+        while left < order.len() && (point.x as i64 - points[order[left]].x as i64) > width {
+            let old: Vector2D = points[order[left]];
+            strip.remove(&(old.y, old.x, order[left]));
+            left += 1;
+        }
+        let low: i32 = (point.y as i64).saturating_sub(width).max(i32::MIN as i64) as i32;
+        let high: i32 = (point.y as i64).saturating_add(width).min(i32::MAX as i64) as i32;
+        for &(y, x, other) in strip.range((low, i32::MIN, 0)..=(high, i32::MAX, usize::MAX)) {
+            keep_pair(&mut best, squared_distance(point, Vector2D { x, y }), i, other, true);
+        }
+        strip.insert((point.y, point.x, i));
+    }
+    best.map(|(_, a, b)| (a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::{Shape, ShapeName};
+
+    #[test]
+    fn test_pairs_match_brute_force() {
+        let mut seed: u64 = 7;
+        for size in [2, 3, 5, 20, 200] {
+            let mut coordinates: Vec<Vector2D> = Vec::new();
+            for _ in 0..size {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                coordinates.push(Vector2D { x: (seed >> 33) as i32 % 50, y: (seed >> 45) as i32 % 50 });
+            }
+            let mut farthest: Option<(i128, usize, usize)> = None;
+            let mut closest: Option<(i128, usize, usize)> = None;
+            for a in 0..size {
+                for b in 0..size {
+                    if coordinates[a] != coordinates[b] {
+                        keep_pair(&mut farthest, squared_distance(coordinates[a], coordinates[b]), a, b, false);
+                        keep_pair(&mut closest, squared_distance(coordinates[a], coordinates[b]), a, b, true);
+                    }
+                }
+            }
+            let shape: Shape = Shape { coordinates: coordinates.clone(), shape_type: ShapeName::Undefined };
+            assert_eq!(farthest_pair(&coordinates, &shape.convex_hull()), farthest.map(|(_, a, b)| (a, b)));
+            assert_eq!(closest_pair(&coordinates), closest.map(|(_, a, b)| (a, b)));
+        }
+    }
+}
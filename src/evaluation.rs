@@ -0,0 +1,270 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! How well the classifier does on strokes whose shape is known.
+//!
+//! A dataset is a directory of recording files named after the shape of their strokes
+//! (`circle.txt`, `unknown.txt`, ...), or of directories named after the shape containing recording
+//! files. Names that are not built-in shapes become [`ShapeName::Custom`].
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::recognizer::Classifier;
+use crate::shape::{Shape, ShapeName};
+use crate::stroke_file::parse_strokes;
+use crate::vector::Vector2D;
+
+/// Extension of the recording files of a dataset.
+const EXTENSION: &str = "txt";
+
+/// A stroke together with the shape it is supposed to be.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Sample {
+    pub label: ShapeName,
+    pub coordinates: Vec<Vector2D>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DatasetError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
+
+impl Error for DatasetError {}
+
+/// Counts of which shape strokes were classified as, by the shape they are.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Evaluation {
+    /// Every shape that was expected or classified, expected ones first.
+    pub labels: Vec<ShapeName>,
+    /// `confusion[expected][classified]`, indexed like `labels`.
+    pub confusion: Vec<Vec<usize>>,
+}
+
+fn label_of(path: &Path) -> ShapeName {
+    let name: String = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    name.parse().unwrap_or(ShapeName::Custom(name))
+}
+
+fn read_samples(path: &Path, label: &ShapeName, samples: &mut Vec<Sample>) -> Result<(), DatasetError> {
+    let error = |message: String| DatasetError { path: path.to_path_buf(), message };
+    let text: String = fs::read_to_string(path).map_err(|io_error| error(io_error.to_string()))?;
+    let strokes: Vec<Vec<Vector2D>> = parse_strokes(&text).map_err(|parse_error| error(parse_error.to_string()))?;
+    samples.extend(strokes.into_iter().map(|coordinates| Sample { label: label.clone(), coordinates }));
+    Ok(())
+}
+
+/// Sorted paths in `directory`: the recording files and, if `directories` is set, the subdirectories.
+fn entries(directory: &Path, directories: bool) -> Result<Vec<PathBuf>, DatasetError> {
+    let error = |message: String| DatasetError { path: directory.to_path_buf(), message };
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for entry in fs::read_dir(directory).map_err(|io_error| error(io_error.to_string()))? {
+        let path: PathBuf = entry.map_err(|io_error| error(io_error.to_string()))?.path();
+        if (directories && path.is_dir()) || path.extension().is_some_and(|extension| extension == EXTENSION) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Loads all strokes of a dataset, in the order of the file names.
+pub fn load_dataset(directory: &Path) -> Result<Vec<Sample>, DatasetError> {
+    let mut samples: Vec<Sample> = Vec::new();
+    for path in entries(directory, true)? {
+        let label: ShapeName = label_of(&path);
+        if path.is_dir() {
+            for file in entries(&path, false)? {
+                read_samples(&file, &label, &mut samples)?;
+            }
+        } else {
+            read_samples(&path, &label, &mut samples)?;
+        }
+    }
+    Ok(samples)
+}
+
+/// Classifies every sample; strokes the classifier rejects count as `Unknown`. Recording files have
+/// no timestamps, so no stroke is rejected for being drawn too fast or too slowly.
+pub fn evaluate(classifier: &Classifier, samples: &[Sample]) -> Evaluation {
+    Evaluation::new(samples.iter().map(|sample| {
+        let shape: Shape = Shape { coordinates: sample.coordinates.clone(), shape_type: ShapeName::Undefined };
+        (sample.label.clone(), classifier.classify(&shape).shape_name)
+    }))
+}
+
+fn json_string(text: &str) -> String {
+    let mut escaped: String = String::from("\"");
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            character if character.is_control() => escaped.push_str(&format!("\\u{:04x}", character as u32)),
+            character => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_number(value: Option<f64>) -> String {
+    value.map_or("null".to_string(), |value| value.to_string())
+}
+
+fn percent(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |value| format!("{:.1}%", value * 100.0))
+}
+
+impl Evaluation {
+    /// Counts pairs of the expected and the classified shape.
+    pub fn new(results: impl IntoIterator<Item = (ShapeName, ShapeName)>) -> Self {
+        let results: Vec<(ShapeName, ShapeName)> = results.into_iter().collect();
+        let mut labels: Vec<ShapeName> = Vec::new();
+        for label in results.iter().map(|(expected, _)| expected).chain(results.iter().map(|(_, classified)| classified)) {
+            if !labels.contains(label) {
+                labels.push(label.clone());
+            }
+        }
+        let index = |label: &ShapeName| labels.iter().position(|other| other == label).unwrap_or_default();
+        let mut confusion: Vec<Vec<usize>> = vec![vec![0; labels.len()]; labels.len()];
+        for (expected, classified) in &results {
+            confusion[index(expected)][index(classified)] += 1;
+        }
+        Evaluation { labels, confusion }
+    }
+
+    pub fn total(&self) -> usize {
+        self.confusion.iter().flatten().sum()
+    }
+
+    pub fn correct(&self) -> usize {
+        (0..self.labels.len()).map(|i| self.confusion[i][i]).sum()
+    }
+
+    /// Share of correctly classified strokes, between 0 and 1; 0 without any.
+    pub fn accuracy(&self) -> f64 {
+        if self.total() == 0 { 0.0 } else { self.correct() as f64 / self.total() as f64 }
+    }
+
+    /// Number of strokes that are `label`.
+    pub fn support(&self, label: &ShapeName) -> usize {
+        self.labels.iter().position(|other| other == label).map_or(0, |i| self.confusion[i].iter().sum())
+    }
+
+    /// Share of the strokes classified as `label` that really are `label`; `None` if there are none.
+    pub fn precision(&self, label: &ShapeName) -> Option<f64> {
+        let i: usize = self.labels.iter().position(|other| other == label)?;
+        let classified: usize = self.confusion.iter().map(|row| row[i]).sum();
+        (classified > 0).then(|| self.confusion[i][i] as f64 / classified as f64)
+    }
+
+    /// Share of the strokes that are `label` that were classified as `label`; `None` if there are none.
+    pub fn recall(&self, label: &ShapeName) -> Option<f64> {
+        let i: usize = self.labels.iter().position(|other| other == label)?;
+        let expected: usize = self.confusion[i].iter().sum();
+        (expected > 0).then(|| self.confusion[i][i] as f64 / expected as f64)
+    }
+
+    pub fn to_json(&self) -> String {
+        let classes: Vec<String> = self.labels.iter().map(|label| {
+            format!("{{\"label\": {}, \"precision\": {}, \"recall\": {}, \"support\": {}}}", json_string(&label.to_string()), json_number(self.precision(label)), json_number(self.recall(label)), self.support(label))
+        }).collect();
+        let labels: Vec<String> = self.labels.iter().map(|label| json_string(&label.to_string())).collect();
+        let rows: Vec<String> = self.confusion.iter().map(|row| format!("[{}]", row.iter().map(usize::to_string).collect::<Vec<String>>().join(", "))).collect();
+        format!(
+            "{{\n  \"total\": {},\n  \"correct\": {},\n  \"accuracy\": {},\n  \"classes\": [\n    {}\n  ],\n  \"labels\": [{}],\n  \"confusion\": [\n    {}\n  ]\n}}\n",
+            self.total(), self.correct(), self.accuracy(), classes.join(",\n    "), labels.join(", "), rows.join(",\n    ")
+        )
+    }
+
+    /// The accuracy stored in a report written by [`Evaluation::to_json`], to compare against.
+    pub fn accuracy_from_json(json: &str) -> Option<f64> {
+        let (_, rest) = json.split_once("\"accuracy\":")?;
+        let end: usize = rest.find([',', '}']).unwrap_or(rest.len());
+        rest[..end].trim().parse().ok()
+    }
+}
+
+impl fmt::Display for Evaluation {
+    /// The accuracy, the precision and recall of every shape, and the confusion matrix with the
+    /// expected shapes as rows and the classified ones as columns.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Accuracy: {} ({} of {})", percent(Some(self.accuracy())), self.correct(), self.total())?;
+        let names: Vec<String> = self.labels.iter().map(ShapeName::to_string).collect();
+        let width: usize = names.iter().map(String::len).chain(["expected".len()]).max().unwrap_or_default();
+        writeln!(f, "\n{:<width$}  {:>9}  {:>7}  {:>7}", "", "precision", "recall", "support")?;
+        for (label, name) in self.labels.iter().zip(&names) {
+            writeln!(f, "{:<width$}  {:>9}  {:>7}  {:>7}", name, percent(self.precision(label)), percent(self.recall(label)), self.support(label))?;
+        }
+        write!(f, "\n{:<width$}", "expected")?;
+        for name in &names {
+            write!(f, "  {:>w$}", name, w = name.len().max(3))?;
+        }
+        writeln!(f)?;
+        for (row, name) in self.confusion.iter().zip(&names) {
+            write!(f, "{:<width$}", name)?;
+            for (count, column) in row.iter().zip(&names) {
+                write!(f, "  {:>w$}", count, w = column.len().max(3))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stroke_file::format_stroke;
+    use crate::synthetic::{Figure, Generator};
+
+    #[test]
+    fn test_evaluation() {
+        let (circle, line, unknown) = (ShapeName::Circle, ShapeName::Line, ShapeName::Unknown);
+        let evaluation: Evaluation = Evaluation::new([(circle.clone(), circle.clone()), (circle.clone(), unknown.clone()), (line.clone(), line.clone()), (line.clone(), circle.clone())]);
+        assert_eq!(evaluation.labels, vec![circle.clone(), line.clone(), unknown.clone()]);
+        assert_eq!(evaluation.confusion, vec![vec![1, 0, 1], vec![1, 1, 0], vec![0, 0, 0]]);
+        assert_eq!((evaluation.accuracy(), evaluation.precision(&circle), evaluation.recall(&circle)), (0.5, Some(0.5), Some(0.5)));
+        assert_eq!((evaluation.precision(&line), evaluation.recall(&unknown), evaluation.support(&line)), (Some(1.0), None, 2));
+        assert_eq!(Evaluation::accuracy_from_json(&evaluation.to_json()), Some(0.5));
+        assert!(evaluation.to_string().contains("Circle        50.0%    50.0%"), "{}", evaluation);
+        assert_eq!(Evaluation::new([]).accuracy(), 0.0);
+
+        let directory: PathBuf = std::env::temp_dir().join(format!("movement-evaluation-{}", std::process::id()));
+        fs::create_dir_all(directory.join("line")).unwrap();
+        let mut generator: Generator = Generator { noise: 0.5, start_jitter: 0.5, ..Generator::default() };
+        let strokes = |generator: &mut Generator, figure: Figure| (0..5).map(|_| format_stroke(&generator.points(figure))).collect::<Vec<String>>().join("\n");
+        fs::write(directory.join("circle.txt"), strokes(&mut generator, Figure::Circle)).unwrap();
+        fs::write(directory.join("ellipse.txt"), strokes(&mut generator, Figure::Ellipse { aspect: 0.5 })).unwrap();
+        fs::write(directory.join("line").join("horizontal.txt"), strokes(&mut generator, Figure::Line)).unwrap();
+        fs::write(directory.join("notes.md"), "not a recording").unwrap();
+        let samples: Result<Vec<Sample>, DatasetError> = load_dataset(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+        let samples: Vec<Sample> = samples.unwrap();
+        assert_eq!(samples.iter().map(|sample| sample.label.clone()).collect::<Vec<ShapeName>>(), [vec![circle; 5], vec![ShapeName::Ellipse; 5], vec![line; 5]].concat());
+        let evaluation: Evaluation = evaluate(&Classifier::default(), &samples);
+        assert!(evaluation.accuracy() > 0.9, "{}", evaluation);
+    }
+}
@@ -0,0 +1,294 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Bindings from recognized shapes to actions, for using Movement-RS as a mouse gesture daemon.
+//!
+//! A bindings file has one binding per line: the shape name, optional conditions and, after
+//! `=>`, the action. The first binding whose shape and conditions match is used.
+//!
+//! ```text
+//! # shape [conditions] => action
+//! circle => run notify-send "Circle"
+//! circle winding=counter-clockwise => event undo
+//! line direction=right min-size=200 => event next-page
+//! ellipse region=0,0,1920,1080 => run xdotool key ctrl+z
+//! zigzag => event delete
+//! ```
+//!
+//! Conditions are `min-size=<px>` and `max-size=<px>` (distance of the two farthest points),
+//! `direction=<right|down-right|down|...>` (from the first to the last point, or from the tail
+//! to the tip of an arrow),
+//! `winding=<clockwise|counter-clockwise>` (for closed shapes) and
+//! `region=<x>,<y>,<width>,<height>` (the centre of the shape must lie inside).
+//! Actions are `run <shell command>` and `event <name>`, which prints `EVENT <name>` to stdout.
+//!
+//! Gestures made of several lines and arcs are defined with `gesture <name> = <pattern>`
+//! (see [`crate::segment`] for the pattern syntax) and can then be bound like shapes:
+//!
+//! ```text
+//! gesture l-shape = D R
+//! gesture check = DR UR
+//! l-shape => event select
+//! check min-size=100 => run notify-send "Done"
+//! ```
+
+use std::fmt;
+use std::io;
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, ExitStatus};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::direction::{Direction, Winding};
+use crate::segment::Pattern;
+use crate::shape::{Shape, ShapeName};
+use crate::stroke_file::ParseError;
+use crate::vector::Vector2F;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Condition {
+    MinSize(f64),
+    MaxSize(f64),
+    Direction(Direction),
+    Winding(Winding),
+    Region { x: f64, y: f64, width: f64, height: f64 },
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Action {
+    Run(String),
+    Event(String),
+}
+
+/// What a binding reacts to: a classified shape or a gesture defined in the bindings file.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Target {
+    Shape(ShapeName),
+    Gesture { name: String, pattern: Pattern },
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Binding {
+    pub target: Target,
+    pub conditions: Vec<Condition>,
+    pub action: Action,
+}
+
+#[derive(Debug)]
+pub enum ActionOutcome {
+    /// Nothing was executed because of the dry-run mode.
+    DryRun,
+    Emitted,
+    Exited(ExitStatus),
+    /// The command did not finish within the timeout and was killed.
+    TimedOut,
+}
+
+/// Runs actions; commands are started with `sh -c` and killed after `timeout`, together with
+/// everything they started on Unix.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Executor {
+    pub timeout: Duration,
+    pub dry_run: bool,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Action::Run(command) => write!(f, "run {}", command),
+            Action::Event(name) => write!(f, "event {}", name),
+        }
+    }
+}
+
+impl Condition {
+    pub fn matches(&self, shape: &Shape) -> bool {
+        if shape.coordinates.is_empty() {
+            return false;
+        }
+        match self {
+            Condition::MinSize(size) => shape.get_distances().max >= *size,
+            Condition::MaxSize(size) => shape.get_distances().max <= *size,
+            Condition::Direction(direction) => match shape.arrow().map(|arrow| arrow.swipe).or_else(|| shape.swipe()) {
+                Some(swipe) if direction.is_diagonal() => swipe.direction == *direction,
+                Some(swipe) => swipe.direction_4 == *direction,
+                None => false,
+            },
+            Condition::Winding(winding) => shape.winding() == Some(*winding),
+            Condition::Region { x, y, width, height } => {
+                let centre: Vector2F = shape.find_centre();
+                centre.x >= *x && centre.y >= *y && centre.x <= x + width && centre.y <= y + height
+            },
+        }
+    }
+}
+
+impl Binding {
+    pub fn matches(&self, shape: &Shape, shape_name: &ShapeName) -> bool {
+        let target: bool = match &self.target {
+            Target::Shape(name) => name == shape_name,
+            Target::Gesture { pattern, .. } => pattern.matches(&shape.segments()),
+        };
+        target && self.conditions.iter().all(|condition| condition.matches(shape))
+    }
+}
+
+/// The first binding for `shape_name`, or for a gesture matching the segments of `shape`,
+/// whose conditions all hold for `shape`.
+pub fn find_binding<'a>(bindings: &'a [Binding], shape: &Shape, shape_name: &ShapeName) -> Option<&'a Binding> {
+    bindings.iter().find(|binding| binding.matches(shape, shape_name))
+}
+
+fn parse_condition(text: &str) -> Result<Condition, String> {
+    let (key, value) = text.split_once('=').ok_or_else(|| format!("expected a condition like `min-size=100`, found `{}`", text))?;
+    let number = |value: &str| value.parse::<f64>().map_err(|_| format!("`{}` is not a number", value));
+    match key {
+        "min-size" => Ok(Condition::MinSize(number(value)?)),
+        "max-size" => Ok(Condition::MaxSize(number(value)?)),
+        "direction" => Ok(Condition::Direction(value.parse()?)),
+        "winding" => Ok(Condition::Winding(value.parse()?)),
+        "region" => match value.split(',').map(number).collect::<Result<Vec<f64>, String>>()?.as_slice() {
+            [x, y, width, height] => Ok(Condition::Region { x: *x, y: *y, width: *width, height: *height }),
+            _ => Err(format!("expected a region like `0,0,1920,1080`, found `{}`", value)),
+        },
+        _ => Err(format!("unknown condition `{}`", key)),
+    }
+}
+
+pub fn parse_bindings(text: &str) -> Result<Vec<Binding>, ParseError> {
+    let mut bindings: Vec<Binding> = Vec::new();
+    let mut gestures: Vec<(String, Pattern)> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line: &str = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: String| ParseError { line: number + 1, message };
+        if let Some(definition) = line.strip_prefix("gesture ") {
+            let (name, pattern) = definition.split_once('=').ok_or_else(|| error(String::from("expected `gesture <name> = <pattern>`")))?;
+            let name: &str = name.trim();
+            if name.is_empty() || name.contains(char::is_whitespace) || name.parse::<ShapeName>().is_ok() {
+                return Err(error(format!("`{}` cannot be used as a gesture name", name)));
+            }
+            gestures.push((name.to_string(), pattern.parse().map_err(error)?));
+            continue;
+        }
+        let (pattern, action) = line.split_once("=>").ok_or_else(|| error(String::from("expected `=>` followed by an action")))?;
+        let mut words = pattern.split_whitespace();
+        let name: &str = words.next().ok_or_else(|| error(String::from("expected a shape name")))?;
+        let target: Target = match gestures.iter().rev().find(|(gesture, _)| gesture == name) {
+            Some((name, pattern)) => Target::Gesture { name: name.clone(), pattern: pattern.clone() },
+            None => Target::Shape(name.parse().map_err(|message| error(format!("{} (gestures must be defined before they are bound)", message)))?),
+        };
+        let conditions: Vec<Condition> = words.map(parse_condition).collect::<Result<Vec<Condition>, String>>().map_err(error)?;
+        let action: Action = match action.trim().split_once(char::is_whitespace) {
+            Some(("run", command)) => Action::Run(command.trim().to_string()),
+            Some(("event", name)) => Action::Event(name.trim().to_string()),
+            _ => return Err(error(format!("expected `run <command>` or `event <name>`, found `{}`", action.trim()))),
+        };
+        bindings.push(Binding { target, conditions, action });
+    }
+    Ok(bindings)
+}
+
+/// Kills the shell and the processes it started, which all run in the process group of the shell.
+#[cfg(unix)]
+fn kill(child: &mut Child) -> io::Result<()> {
+    // SAFETY: `killpg` only sends a signal and does not touch any memory of this process.
+    if unsafe { libc::killpg(child.id() as libc::pid_t, libc::SIGKILL) } == 0 {
+        return Ok(());
+    }
+    let error: io::Error = io::Error::last_os_error();
+    // The group is gone if everything in it has exited in the meantime.
+    if error.raw_os_error() == Some(libc::ESRCH) { Ok(()) } else { Err(error) }
+}
+
+#[cfg(not(unix))]
+fn kill(child: &mut Child) -> io::Result<()> {
+    child.kill()
+}
+
+impl Executor {
+    pub fn execute(&self, action: &Action) -> io::Result<ActionOutcome> {
+        if self.dry_run {
+            return Ok(ActionOutcome::DryRun);
+        }
+        match action {
+            Action::Event(name) => {
+                println!("EVENT {}", name);
+                Ok(ActionOutcome::Emitted)
+            },
+            Action::Run(command) => {
+                let mut shell: Command = Command::new("sh");
+                shell.arg("-c").arg(command);
+                #[cfg(unix)]
+                shell.process_group(0);
+                let mut child: Child = shell.spawn()?;
+                let start: Instant = Instant::now();
+                loop {
+                    if let Some(status) = child.try_wait()? {
+                        return Ok(ActionOutcome::Exited(status));
+                    }
+                    if start.elapsed() >= self.timeout {
+                        kill(&mut child)?;
+                        child.wait()?;
+                        return Ok(ActionOutcome::TimedOut);
+                    }
+                    thread::sleep(Duration::from_millis(10));
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::Vector2D;
+
+    #[test]
+    fn test_bindings() {
+        let bindings: Vec<Binding> = parse_bindings("# gestures\nline direction=left => event back\nline direction=right min-size=200 region=0,0,1000,1000 => run true\ncircle => run sleep 5\n").unwrap();
+        assert_eq!(bindings.len(), 3);
+        assert_eq!(bindings[1].conditions[1], Condition::MinSize(200.0));
+        let line: Shape = Shape { coordinates: vec![Vector2D { x: 100, y: 500 }, Vector2D { x: 400, y: 520 }], shape_type: ShapeName::Undefined };
+        assert_eq!(find_binding(&bindings, &line, &ShapeName::Line).map(|binding| &binding.action), Some(&Action::Run(String::from("true"))));
+        assert_eq!(find_binding(&bindings, &line, &ShapeName::Ellipse), None);
+        assert_eq!(parse_bindings("square => event x").unwrap_err().line, 1);
+        assert!(parse_bindings("line size=3 => event x").unwrap_err().message.contains("size"));
+
+        let executor: Executor = Executor { timeout: Duration::from_millis(100), dry_run: false };
+        assert!(matches!(Executor { dry_run: true, ..executor }.execute(&bindings[2].action), Ok(ActionOutcome::DryRun)));
+        #[cfg(unix)]
+        {
+            assert!(matches!(executor.execute(&bindings[1].action), Ok(ActionOutcome::Exited(status)) if status.success()));
+            // The timeout also kills what the command started in the background.
+            let marker: std::path::PathBuf = std::env::temp_dir().join(format!("movement-timeout-{}", std::process::id()));
+            let action: Action = Action::Run(format!("(sleep 0.5; touch '{}') & wait", marker.display()));
+            assert!(matches!(executor.execute(&action), Ok(ActionOutcome::TimedOut)));
+            thread::sleep(Duration::from_millis(800));
+            assert!(!marker.exists());
+        }
+
+        let bindings: Vec<Binding> = parse_bindings("gesture l-shape = D R\nl-shape => event select\nline => event line\n").unwrap();
+        let l_shape: Shape = Shape { coordinates: (0..=60).map(|i| Vector2D { x: 100 + 5 * (i - 30).max(0), y: 100 + 5 * i.min(30) }).collect(), shape_type: ShapeName::Undefined };
+        assert_eq!(find_binding(&bindings, &l_shape, &ShapeName::Unknown).map(|binding| &binding.action), Some(&Action::Event(String::from("select"))));
+        assert_eq!(find_binding(&bindings, &line, &ShapeName::Line).map(|binding| &binding.action), Some(&Action::Event(String::from("line"))));
+        assert_eq!(parse_bindings("gesture circle = R").unwrap_err().line, 1);
+        assert!(parse_bindings("gesture z = R Q").unwrap_err().message.contains("`Q`"));
+    }
+}
@@ -0,0 +1,232 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Recognition of single-stroke characters by comparing the stroke with templates.
+//!
+//! Both the stroke and the templates are resampled to the same number of evenly spaced points,
+//! moved to their centroid and scaled uniformly to a unit box; the score of a template is based
+//! on the mean distance between corresponding points. Rotation is not normalized, since it
+//! tells characters like `6` and `9` apart, and neither is the drawing direction.
+//!
+//! Templates are written as paths in a box from `0 0` (top left) to `1 1` (bottom right):
+//! `M x y` moves to a point, `L x y` draws a line to it and `A cx cy rx ry from to` draws an
+//! elliptical arc from the angle `from` to `to` in degrees (0 is right, 90 is down).
+
+use std::f64::consts::SQRT_2;
+use std::fmt;
+
+use crate::curvature::resample_points;
+use crate::shape::Shape;
+use crate::vector::Vector2F;
+
+/// Number of points strokes and templates are resampled to before they are compared.
+const GLYPH_POINTS: usize = 64;
+/// Angle between two points of a template arc, in degrees.
+const ARC_STEP_DEG: f64 = 5.0;
+
+/// Digits as most people write them in one stroke.
+const DIGITS: [(char, &str); 10] = [
+    ('0', "A 0.5 0.5 0.35 0.5 -90 -450"),
+    ('1', "M 0.5 0 L 0.5 1"),
+    ('2', "A 0.5 0.3 0.35 0.3 -180 0 L 0 1 L 1 1"),
+    ('3', "A 0.5 0.25 0.35 0.25 -150 90 A 0.5 0.75 0.4 0.25 -90 150"),
+    ('4', "M 0.7 0 L 0 0.65 L 1 0.65 L 0.7 0.65 L 0.7 1"),
+    ('5', "M 0.9 0 L 0.2 0 L 0.17 0.49 A 0.45 0.7 0.4 0.3 -135 150"),
+    ('6', "M 0.75 0 L 0.35 0.3 L 0.15 0.7 A 0.5 0.7 0.35 0.3 180 -180"),
+    ('7', "M 0 0 L 1 0 L 0.35 1"),
+    ('8', "A 0.5 0.25 0.3 0.25 -30 -270 A 0.5 0.75 0.35 0.25 -90 270 L 0.76 0.125"),
+    ('9', "A 0.5 0.3 0.3 0.3 0 -360 L 0.8 1"),
+];
+
+/// Unistroke letters in the style of Graffiti.
+const LETTERS: [(char, &str); 26] = [
+    ('A', "M 0 1 L 0.5 0 L 1 1"),
+    ('B', "M 0 0 L 0 1 L 0 0 A 0 0.25 0.5 0.25 -90 90 A 0 0.75 0.5 0.25 -90 90"),
+    ('C', "A 0.55 0.5 0.45 0.5 -45 -315"),
+    ('D', "M 0 1 L 0 0 A 0 0.5 1 0.5 -90 90"),
+    ('E', "A 0.55 0.27 0.4 0.23 -30 -270 A 0.55 0.75 0.45 0.25 -90 -330"),
+    ('F', "M 1 0 L 0 0 L 0 1"),
+    ('G', "A 0.5 0.5 0.45 0.5 -45 -360 L 0.55 0.5"),
+    ('H', "M 0 0 L 0 1 L 0 0.65 A 0.5 0.65 0.5 0.3 180 360 L 1 1"),
+    ('I', "M 0.5 0 L 0.5 1"),
+    ('J', "M 1 0 L 1 0.7 A 0.6 0.7 0.4 0.3 0 180"),
+    ('K', "M 1 0 L 0 0.5 L 1 1"),
+    ('L', "M 0 0 L 0 1 L 1 1"),
+    ('M', "M 0 1 L 0 0 L 0.5 0.6 L 1 0 L 1 1"),
+    ('N', "M 0 1 L 0 0 L 1 1 L 1 0"),
+    ('O', "A 0.5 0.5 0.5 0.5 -90 -450"),
+    ('P', "M 0 1 L 0 0 A 0 0.25 0.6 0.25 -90 90"),
+    ('Q', "A 0.5 0.45 0.45 0.45 -90 -450 L 1 1"),
+    ('R', "M 0 1 L 0 0 A 0 0.25 0.6 0.25 -90 90 L 1 1"),
+    ('S', "A 0.5 0.25 0.4 0.25 -30 -270 A 0.5 0.75 0.4 0.25 -90 150"),
+    ('T', "M 0 0 L 1 0 L 0.5 0 L 0.5 1"),
+    ('U', "M 0 0 L 0 0.6 A 0.5 0.6 0.5 0.4 180 0 L 1 0"),
+    ('V', "M 0 0 L 0.5 1 L 1 0"),
+    ('W', "M 0 0 L 0.25 1 L 0.5 0.3 L 0.75 1 L 1 0"),
+    ('X', "M 0 0 L 1 1 L 1 0 L 0 1"),
+    ('Y', "M 0 0 L 0.5 0.5 L 1 0 L 0.5 0.5 L 0.5 1"),
+    ('Z', "M 0 0 L 1 0 L 0 1 L 1 1"),
+];
+
+/// A character and the normalized points it is drawn with.
+#[derive(Clone, PartialEq, Debug)]
+pub struct GlyphTemplate {
+    pub character: char,
+    pub points: Vec<Vector2F>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GlyphCandidate {
+    pub character: char,
+    /// How well the stroke matched the template, in percent.
+    pub score: f64,
+}
+
+/// The best matching characters for a stroke, best first.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Glyph {
+    pub candidates: Vec<GlyphCandidate>,
+}
+
+/// Resamples `points` and moves and scales them into the frame templates are compared in.
+fn normalize(points: &[Vector2F]) -> Vec<Vector2F> {
+    let mut resampled: Vec<Vector2F> = resample_points(points, GLYPH_POINTS).into_iter().map(|(point, _)| point).collect();
+    if let Some(&last) = resampled.last() {
+        resampled.resize(GLYPH_POINTS, last);
+    }
+    let centre: Vector2F = resampled.iter().fold(Vector2F::default(), |sum, point| sum + *point) / resampled.len().max(1) as f64;
+    let mut min: Vector2F = Vector2F::new(f64::MAX, f64::MAX);
+    let mut max: Vector2F = Vector2F::new(f64::MIN, f64::MIN);
+    for point in &resampled {
+        min = Vector2F::new(min.x.min(point.x), min.y.min(point.y));
+        max = Vector2F::new(max.x.max(point.x), max.y.max(point.y));
+    }
+    let size: f64 = (max.x - min.x).max(max.y - min.y);
+    let scale: f64 = if size > 0.0 { 1.0 / size } else { 1.0 };
+    resampled.into_iter().map(|point| (point - centre) * scale).collect()
+}
+
+/// Parses a template path (see the module documentation) into points.
+pub fn parse_path(path: &str) -> Result<Vec<Vector2F>, String> {
+    let mut points: Vec<Vector2F> = Vec::new();
+    let mut words = path.split_whitespace();
+    while let Some(command) = words.next() {
+        let count: usize = match command {
+            "M" | "L" => 2,
+            "A" => 6,
+            _ => return Err(format!("unknown path command `{}`", command)),
+        };
+        let numbers: Vec<f64> = words.by_ref().take(count).map(|word| word.parse::<f64>().map_err(|_| format!("`{}` is not a number", word))).collect::<Result<Vec<f64>, String>>()?;
+        match numbers.as_slice() {
+            [x, y] => points.push(Vector2F::new(*x, *y)),
+            [cx, cy, rx, ry, from, to] => {
+                let steps: usize = ((to - from).abs() / ARC_STEP_DEG).ceil().max(1.0) as usize;
+                points.extend((0..=steps).map(|i| {
+                    let angle: f64 = (from + (to - from) * i as f64 / steps as f64).to_radians();
+                    Vector2F::new(cx + rx * angle.cos(), cy + ry * angle.sin())
+                }));
+            },
+            _ => return Err(format!("`{}` expects {} numbers", command, count)),
+        }
+    }
+    if points.len() < 2 {
+        return Err(String::from("a path needs at least two points"));
+    }
+    Ok(points)
+}
+
+impl GlyphTemplate {
+    pub fn new(character: char, points: &[Vector2F]) -> Self {
+        GlyphTemplate { character, points: normalize(points) }
+    }
+
+    pub fn from_path(character: char, path: &str) -> Result<Self, String> {
+        Ok(GlyphTemplate::new(character, &parse_path(path)?))
+    }
+}
+
+fn templates(paths: &[(char, &str)]) -> Vec<GlyphTemplate> {
+    paths.iter().map(|(character, path)| GlyphTemplate::from_path(*character, path).unwrap_or_else(|error| panic!("invalid built-in glyph `{}`: {}", character, error))).collect()
+}
+
+pub fn digit_templates() -> Vec<GlyphTemplate> {
+    templates(&DIGITS)
+}
+
+pub fn letter_templates() -> Vec<GlyphTemplate> {
+    templates(&LETTERS)
+}
+
+/// Digits and letters; `1` and `I` as well as `0` and `O` are drawn the same way and score alike.
+pub fn builtin_templates() -> Vec<GlyphTemplate> {
+    let mut templates: Vec<GlyphTemplate> = digit_templates();
+    templates.extend(letter_templates());
+    templates
+}
+
+impl Glyph {
+    pub fn best(&self) -> Option<GlyphCandidate> {
+        self.candidates.first().copied()
+    }
+}
+
+impl fmt::Display for Glyph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let candidates: Vec<String> = self.candidates.iter().map(|candidate| format!("{} ({}%)", candidate.character, candidate.score as i32)).collect();
+        f.write_str(&candidates.join(", "))
+    }
+}
+
+impl Shape {
+    /// The `count` templates that match the stroke best, best first.
+    pub fn glyph(&self, templates: &[GlyphTemplate], count: usize) -> Glyph {
+        if self.coordinates.is_empty() {
+            return Glyph { candidates: Vec::new() };
+        }
+        let points: Vec<Vector2F> = normalize(&self.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect::<Vec<Vector2F>>());
+        let mut candidates: Vec<GlyphCandidate> = templates.iter().map(|template| {
+            let distance: f64 = points.iter().zip(&template.points).map(|(a, b)| a.distance(*b)).sum::<f64>() / GLYPH_POINTS as f64;
+            // Half the diagonal of the unit box is the largest distance that still scores.
+            GlyphCandidate { character: template.character, score: (1.0 - distance / (SQRT_2 / 2.0)).max(0.0) * 100.0 }
+        }).collect();
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates.truncate(count);
+        Glyph { candidates }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::ShapeName;
+    use crate::vector::Vector2D;
+
+    #[test]
+    fn test_glyphs() {
+        for (paths, templates) in [(&DIGITS[..], digit_templates()), (&LETTERS[..], letter_templates())] {
+            assert_eq!(templates.len(), paths.len());
+            for (character, path) in paths {
+                // Drawn 200 px high, slightly sheared, at a random place on the screen.
+                let coordinates: Vec<Vector2D> = parse_path(path).unwrap().into_iter().map(|point| Vector2F::new(700.0 + 150.0 * point.x + 20.0 * point.y, 300.0 + 200.0 * point.y).round()).collect();
+                let glyph: Glyph = Shape { coordinates, shape_type: ShapeName::Undefined }.glyph(&templates, 3);
+                assert_eq!(glyph.candidates.len(), 3);
+                assert_eq!(glyph.best().map(|candidate| candidate.character), Some(*character), "{}", glyph);
+            }
+        }
+        assert!(parse_path("M 0 0 Q 1 1").unwrap_err().contains("`Q`"));
+        assert!(Shape { coordinates: Vec::new(), shape_type: ShapeName::Undefined }.glyph(&builtin_templates(), 3).best().is_none());
+    }
+}
@@ -0,0 +1,201 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::shape::Shape;
+use crate::vector::{Vector2D, Vector2F};
+
+/// A part of the stroke that lies inside its convex hull, between two hull vertices.
+/// All values are indices into `Shape.coordinates`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ConvexityDefect {
+    pub start: usize,
+    pub end: usize,
+    pub farthest: usize,
+    /// Distance of the farthest point to the hull edge from `start` to `end`.
+    pub depth: f64,
+}
+
+/// The smallest rectangle, in any rotation, that contains all points of the stroke.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BoundingRectangle {
+    pub corners: [Vector2F; 4],
+    /// Length of the side parallel to `angle`.
+    pub width: f64,
+    pub height: f64,
+    /// Angle of the `width` side against the x axis in radians.
+    pub angle: f64,
+    pub area: f64,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct HullDescriptors {
+    pub area: f64,
+    pub perimeter: f64,
+    /// Area enclosed by the (closed) stroke divided by the hull area.
+    pub solidity: f64,
+    /// Hull perimeter divided by the length of the (closed) stroke.
+    pub convexity: f64,
+    pub defects: Vec<ConvexityDefect>,
+    pub bounding_rectangle: BoundingRectangle,
+}
+
+/// Signed area of the polygon through `points` (shoelace formula).
+/// Positive if the points are counter-clockwise in a y-up coordinate system.
+pub fn signed_area(points: &[Vector2F]) -> f64 {
+    if points.len() < 3 {
+        return 0.0;
+    }
+    let mut area: f64 = 0.0;
+    for i in 0..points.len() {
+        area += points[i].cross(points[(i + 1) % points.len()]);
+    }
+    area / 2.0
+}
+
+/// Length of the closed polygon through `points`.
+pub fn closed_length(points: &[Vector2F]) -> f64 {
+    if points.len() < 2 {
+        return 0.0;
+    }
+    (0..points.len()).map(|i| points[i].distance(points[(i + 1) % points.len()])).sum()
+}
+
+fn turn(o: Vector2D, a: Vector2D, b: Vector2D) -> i128 {
+    (a.x as i128 - o.x as i128) * (b.y as i128 - o.y as i128) - (a.y as i128 - o.y as i128) * (b.x as i128 - o.x as i128)
+}
+
+impl Shape {
+    /// Convex hull of the stroke (Andrew's monotone chain) as indices into `coordinates`,
+    /// counter-clockwise in a y-up coordinate system and without collinear points.
+    pub fn convex_hull(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.coordinates.len()).collect();
+        order.sort_by_key(|&i| (self.coordinates[i].x, self.coordinates[i].y, i));
+        order.dedup_by_key(|i| self.coordinates[*i]);
+        if order.len() < 3 {
+            return order;
+        }
+        let mut hull: Vec<usize> = Vec::with_capacity(order.len() * 2);
+        for pass in [order.clone(), order.iter().rev().copied().collect()] {
+            let lower_length: usize = hull.len();
+            for i in pass {
+                while hull.len() >= lower_length + 2 && turn(self.coordinates[hull[hull.len()-2]], self.coordinates[hull[hull.len()-1]], self.coordinates[i]) <= 0 {
+                    hull.pop();
+                }
+                hull.push(i);
+            }
+            hull.pop();
+        }
+        hull
+    }
+
+    pub fn hull_descriptors(&self) -> HullDescriptors {
+        let hull: Vec<usize> = self.convex_hull();
+        let hull_points: Vec<Vector2F> = hull.iter().map(|&i| self.coordinates[i].to_f64()).collect();
+        let stroke: Vec<Vector2F> = self.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+        let area: f64 = signed_area(&hull_points).abs();
+        let perimeter: f64 = closed_length(&hull_points);
+        let stroke_length: f64 = closed_length(&stroke);
+        HullDescriptors {
+            area,
+            perimeter,
+            solidity: if area > 0.0 { signed_area(&stroke).abs() / area } else { 1.0 },
+            convexity: if stroke_length > 0.0 { perimeter / stroke_length } else { 1.0 },
+            defects: self.convexity_defects(&hull),
+            bounding_rectangle: minimum_bounding_rectangle(&hull_points),
+        }
+    }
+
+    fn convexity_defects(&self, hull: &[usize]) -> Vec<ConvexityDefect> {
+        let mut vertices: Vec<usize> = hull.to_vec();
+        vertices.sort_unstable();
+        let mut defects: Vec<ConvexityDefect> = Vec::new();
+        if vertices.len() < 2 {
+            return defects;
+        }
+        let length: usize = self.coordinates.len();
+        for (position, &start) in vertices.iter().enumerate() {
+            let end: usize = vertices[(position + 1) % vertices.len()];
+            let (a, b) = (self.coordinates[start].to_f64(), self.coordinates[end].to_f64());
+            let mut defect: Option<ConvexityDefect> = None;
+            let mut i: usize = (start + 1) % length;
+            while i != end {
+                let depth: f64 = self.coordinates[i].to_f64().distance_to_line(a, b).abs();
+                if depth > defect.map_or(0.0, |defect| defect.depth) {
+                    defect = Some(ConvexityDefect { start, end, farthest: i, depth });
+                }
+                i = (i + 1) % length;
+            }
+            defects.extend(defect);
+        }
+        defects
+    }
+}
+
+/// Rotating calipers over the hull edges; the minimum-area rectangle has one side on a hull edge.
+fn minimum_bounding_rectangle(hull: &[Vector2F]) -> BoundingRectangle {
+    let mut best: BoundingRectangle = BoundingRectangle { corners: [hull.first().copied().unwrap_or_default(); 4], width: 0.0, height: 0.0, angle: 0.0, area: f64::MAX };
+    for i in 0..hull.len() {
+        let edge: Vector2F = (hull[(i + 1) % hull.len()] - hull[i]).normalize();
+        if edge == Vector2F::default() {
+            continue;
+        }
+        let normal: Vector2F = Vector2F::new(-edge.y, edge.x);
+        let (mut min_u, mut max_u, mut min_v, mut max_v) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+        for point in hull {
+            let (u, v) = (point.dot(edge), point.dot(normal));
+            min_u = min_u.min(u);
+            max_u = max_u.max(u);
+            min_v = min_v.min(v);
+            max_v = max_v.max(v);
+        }
+        let area: f64 = (max_u - min_u) * (max_v - min_v);
+        if area < best.area {
+            let corner = |u: f64, v: f64| edge * u + normal * v;
+            best = BoundingRectangle {
+                corners: [corner(min_u, min_v), corner(max_u, min_v), corner(max_u, max_v), corner(min_u, max_v)],
+                width: max_u - min_u,
+                height: max_v - min_v,
+                angle: edge.angle(),
+                area,
+            };
+        }
+    }
+    if best.area == f64::MAX {
+        best.area = 0.0;
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::ShapeName;
+
+    #[test]
+    fn test_hull_descriptors() {
+        let coordinates: Vec<Vector2D> = vec![Vector2D { x: 0, y: 0 }, Vector2D { x: 100, y: 0 }, Vector2D { x: 50, y: 10 }, Vector2D { x: 100, y: 100 }, Vector2D { x: 0, y: 100 }, Vector2D { x: 50, y: 50 }, Vector2D { x: 0, y: 0 }];
+        let shape: Shape = Shape { coordinates, shape_type: ShapeName::Undefined };
+        assert_eq!(shape.convex_hull(), vec![0, 1, 3, 4]);
+        let descriptors: HullDescriptors = shape.hull_descriptors();
+        assert_eq!(descriptors.area, 10000.0);
+        assert_eq!(descriptors.perimeter, 400.0);
+        assert!((descriptors.solidity - 0.5).abs() < 1e-9);
+        assert_eq!(descriptors.defects.len(), 2);
+        assert_eq!((descriptors.defects[0].farthest, descriptors.defects[0].depth), (2, 50.0));
+        assert_eq!((descriptors.defects[1].farthest, descriptors.defects[1].depth), (5, 50.0));
+        assert!((descriptors.bounding_rectangle.area - 10000.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,84 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+
+pub mod annotation;
+pub mod arrow;
+pub mod calibration;
+pub mod catalogue;
+pub mod curvature;
+pub mod direction;
+pub mod distances;
+pub mod evaluation;
+pub mod gestures;
+pub mod glyph;
+pub mod hull;
+pub mod limits;
+pub mod normalize;
+pub mod pca;
+pub mod primitive;
+pub mod raster;
+pub mod recognizer;
+pub mod recording;
+pub mod segment;
+pub mod shape;
+pub mod spatial;
+pub mod star;
+pub mod stroke_file;
+pub mod svg;
+pub mod synthetic;
+pub mod tui;
+pub mod vector;
+
+pub use arrow::Arrow;
+pub use calibration::Calibration;
+pub use curvature::{Corner, Curvature};
+pub use direction::{Direction, Winding};
+pub use evaluation::{DatasetError, Evaluation, Sample};
+pub use glyph::{Glyph, GlyphCandidate, GlyphTemplate};
+pub use hull::{BoundingRectangle, ConvexityDefect, HullDescriptors};
+pub use limits::{Limits, Rejection};
+pub use normalize::{NormalizedShape, Transform};
+pub use pca::{AxisEllipse, MajorAxis, PrincipalComponents};
+pub use primitive::Primitive;
+pub use recognizer::{Calibrated, Classifier, Hypothesis, ShapeRecognizer};
+pub use recording::{Recording, RecordingStatus};
+pub use segment::{Pattern, Segment, SegmentKind};
+pub use shape::{BoundingBox, Classification, Shape, ShapeName, Swipe};
+pub use spatial::KdTree;
+pub use star::Star;
+pub use synthetic::{Figure, Generator, SyntheticStroke};
+pub use vector::{Vector2D, Vector2F};
+
+pub const END_FIGURE_TIMEOUT: u8 = 5;
+pub const FRAMERATE_FPS: u64 = 20;
+pub const TOLERANCE_GENERAL: f64 = 0.25;
+pub const CIRCLE_TOLERANCE: f64 = 0.25;
+pub const LINE_TOLERANCE_PX: f64 = 10.0;
+/// How far points of an ellipse may be from the fitted one, relative to its size in their direction.
+pub const ELLIPSE_TOLERANCE: f64 = 0.1;
+/// Strokes whose fitted ellipse is at least this round (minor divided by major radius) are circles, not ellipses.
+pub const CIRCLE_MIN_ASPECT: f64 = 0.85;
+/// Most corners a circle may have where a shaky hand kinked it; polygons have at least three.
+pub const CIRCLE_MAX_CORNERS: usize = 2;
+pub const CORNER_ANGLE_DEG: f64 = 45.0;
+pub const MIN_POINTS: usize = 5;
+/// Below this probability of the most probable shape from `Classifier::rank`, a stroke is ambiguous
+/// and the TUI lists the alternatives.
+pub const AMBIGUOUS_PROBABILITY: f64 = 0.8;
+pub const MIN_SIZE_PX: f64 = 20.0;
+pub const MIN_SPEED_PX_PER_S: f64 = 20.0;
+pub const MAX_SPEED_PX_PER_S: f64 = 20000.0;
@@ -0,0 +1,115 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::shape::Shape;
+use crate::{MAX_SPEED_PX_PER_S, MIN_POINTS, MIN_SIZE_PX, MIN_SPEED_PX_PER_S};
+
+/// Why a stroke was not classified at all.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rejection {
+    TooFewPoints,
+    /// All points are the same.
+    ZeroLength,
+    /// Both sides of the bounding box are shorter than the minimum size.
+    TooSmall,
+    TooFast,
+    TooSlow,
+}
+
+/// What a stroke needs to be classified. The speed is the length of the stroke divided by the time
+/// it took to draw, so it is only checked if that time is known.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Limits {
+    pub min_points: usize,
+    pub min_size_px: f64,
+    pub min_speed_px_per_s: f64,
+    pub max_speed_px_per_s: f64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits { min_points: MIN_POINTS, min_size_px: MIN_SIZE_PX, min_speed_px_per_s: MIN_SPEED_PX_PER_S, max_speed_px_per_s: MAX_SPEED_PX_PER_S }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Rejection::TooFewPoints => "too few points",
+            Rejection::ZeroLength => "zero length",
+            Rejection::TooSmall => "too small",
+            Rejection::TooFast => "too fast",
+            Rejection::TooSlow => "too slow",
+        })
+    }
+}
+
+impl Limits {
+    /// The first limit `shape` breaks, if any. A stroke needs at least one point and a length above 0
+    /// whatever the limits are, so the recognizers never see an empty or degenerate stroke.
+    pub fn check(&self, shape: &Shape, duration: Option<Duration>) -> Option<Rejection> {
+        if shape.coordinates.is_empty() || shape.coordinates.len() < self.min_points {
+            return Some(Rejection::TooFewPoints);
+        }
+        let length: f64 = shape.coordinates.windows(2).map(|pair| pair[0].to_f64().distance(pair[1].to_f64())).sum();
+        if length <= 0.0 {
+            return Some(Rejection::ZeroLength);
+        }
+        let bounding_box = shape.bounding_box();
+        if bounding_box.width().max(bounding_box.height()) < self.min_size_px {
+            return Some(Rejection::TooSmall);
+        }
+        let speed: f64 = length / duration?.as_secs_f64();
+        if speed > self.max_speed_px_per_s {
+            Some(Rejection::TooFast)
+        } else if speed < self.min_speed_px_per_s {
+            Some(Rejection::TooSlow)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::ShapeName;
+    use crate::vector::Vector2D;
+
+    #[test]
+    fn test_limits() {
+        let shape = |coordinates: &[(i32, i32)]| Shape { coordinates: coordinates.iter().map(|&(x, y)| Vector2D { x, y }).collect(), shape_type: ShapeName::Undefined };
+        let limits: Limits = Limits::default();
+        let line: Shape = shape(&[(100, 100), (150, 100), (200, 100), (250, 100), (300, 100)]);
+        assert_eq!(limits.check(&line, None), None);
+        assert_eq!(limits.check(&line, Some(Duration::from_millis(500))), None);
+        assert_eq!(limits.check(&line, Some(Duration::from_millis(1))), Some(Rejection::TooFast));
+        assert_eq!(limits.check(&line, Some(Duration::from_secs(60))), Some(Rejection::TooSlow));
+        assert_eq!(limits.check(&line, Some(Duration::ZERO)), Some(Rejection::TooFast));
+        assert_eq!(limits.check(&shape(&[]), None), Some(Rejection::TooFewPoints));
+        assert_eq!(limits.check(&shape(&[(5, 5), (10, 5), (15, 5)]), None), Some(Rejection::TooFewPoints));
+        assert_eq!(limits.check(&shape(&[(5, 5); 10]), None), Some(Rejection::ZeroLength));
+        assert_eq!(limits.check(&shape(&[(5, 5), (6, 5), (7, 6), (8, 6), (9, 5)]), None), Some(Rejection::TooSmall));
+        // Without limits, only the empty and the zero-length stroke are rejected.
+        let none: Limits = Limits { min_points: 0, min_size_px: 0.0, min_speed_px_per_s: 0.0, max_speed_px_per_s: f64::INFINITY };
+        assert_eq!(limits.check(&shape(&[]), None), none.check(&shape(&[]), None));
+        assert_eq!(none.check(&shape(&[(5, 5)]), Some(Duration::ZERO)), Some(Rejection::ZeroLength));
+        assert_eq!(none.check(&shape(&[(5, 5), (6, 5)]), Some(Duration::ZERO)), None);
+    }
+}
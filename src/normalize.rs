@@ -0,0 +1,117 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::shape::Shape;
+use crate::vector::Vector2F;
+
+/// Similarity transform from screen coordinates into a normalized frame.
+/// A screen point `p` is mapped to `(p - translation).rotate(rotation) * scale`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Transform {
+    pub translation: Vector2F,
+    pub rotation: f64,
+    pub scale: f64,
+}
+
+/// A copy of a stroke in a canonical frame, together with the transform that produced it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NormalizedShape {
+    pub coordinates: Vec<Vector2F>,
+    pub transform: Transform,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform { translation: Vector2F::default(), rotation: 0.0, scale: 1.0 }
+    }
+}
+
+impl Transform {
+    /// Maps a screen point into the normalized frame.
+    pub fn apply(&self, point: Vector2F) -> Vector2F {
+        (point - self.translation).rotate(self.rotation) * self.scale
+    }
+
+    /// Maps a point of the normalized frame back to screen coordinates.
+    pub fn invert(&self, point: Vector2F) -> Vector2F {
+        (point / self.scale).rotate(-self.rotation) + self.translation
+    }
+}
+
+impl Shape {
+    /// Angle between the centroid and the first point of the stroke.
+    pub fn indicative_angle(&self) -> f64 {
+        match self.coordinates.first() {
+            Some(first) => (first.to_f64() - self.find_centre()).angle(),
+            None => 0.0,
+        }
+    }
+
+    /// Copy of the stroke translated so that its centroid is the origin.
+    pub fn centred(&self) -> NormalizedShape {
+        self.normalized_with_rotation(0.0, false)
+    }
+
+    /// Copy of the stroke translated to its centroid, rotated so that the first point lies on the
+    /// positive x axis and scaled uniformly so that the larger side of its bounding box is 1.
+    pub fn normalized(&self) -> NormalizedShape {
+        self.normalized_with_rotation(-self.indicative_angle(), true)
+    }
+
+    pub(crate) fn normalized_with_rotation(&self, rotation: f64, scale_to_unit: bool) -> NormalizedShape {
+        if self.coordinates.is_empty() {
+            return NormalizedShape { coordinates: Vec::new(), transform: Transform::default() };
+        }
+        let mut transform: Transform = Transform { translation: self.find_centre(), rotation, scale: 1.0 };
+        let mut coordinates: Vec<Vector2F> = self.coordinates.iter().map(|coordinate| transform.apply(coordinate.to_f64())).collect();
+        if scale_to_unit {
+            let mut min: Vector2F = Vector2F::new(f64::MAX, f64::MAX);
+            let mut max: Vector2F = Vector2F::new(f64::MIN, f64::MIN);
+            for coordinate in &coordinates {
+                min = Vector2F::new(min.x.min(coordinate.x), min.y.min(coordinate.y));
+                max = Vector2F::new(max.x.max(coordinate.x), max.y.max(coordinate.y));
+            }
+            let size: f64 = (max.x - min.x).max(max.y - min.y);
+            if size > 0.0 {
+                transform.scale = 1.0 / size;
+                for coordinate in &mut coordinates {
+                    *coordinate = *coordinate * transform.scale;
+                }
+            }
+        }
+        NormalizedShape { coordinates, transform }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::ShapeName;
+    use crate::vector::Vector2D;
+
+    #[test]
+    fn test_normalized_round_trip() {
+        let coordinates: Vec<Vector2D> = vec![Vector2D { x: 300, y: 200 }, Vector2D { x: 200, y: 300 }, Vector2D { x: 100, y: 200 }, Vector2D { x: 200, y: 100 }];
+        let shape: Shape = Shape { coordinates: coordinates.clone(), shape_type: ShapeName::Undefined };
+        let normalized: NormalizedShape = shape.normalized();
+        assert!((normalized.transform.scale - 0.005).abs() < 1e-12);
+        assert!((normalized.coordinates[0] - Vector2F::new(0.5, 0.0)).abs() < 1e-12);
+        for (point, original) in normalized.coordinates.iter().zip(&coordinates) {
+            assert!(point.x.abs() <= 0.5 + 1e-12 && point.y.abs() <= 0.5 + 1e-12);
+            assert_eq!(normalized.transform.invert(*point).round(), *original);
+        }
+    }
+}
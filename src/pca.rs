@@ -0,0 +1,186 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::f64::consts::FRAC_PI_2;
+
+use crate::shape::Shape;
+use crate::vector::Vector2F;
+
+/// Principal component analysis of the points of a stroke.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PrincipalComponents {
+    pub centre: Vector2F,
+    /// Covariance matrix as `[xx, xy, yy]`.
+    pub covariance: [f64; 3],
+    /// Unit vectors of the major and the minor axis.
+    pub axes: [Vector2F; 2],
+    /// Variances along the major and the minor axis.
+    pub eigenvalues: [f64; 2],
+    /// `1 - sqrt(minor / major)`: 0 for round point clouds, 1 for perfectly straight ones.
+    pub elongation: f64,
+    /// Angle of the major axis against the x axis in radians, in `(-π/2, π/2]`.
+    pub orientation: f64,
+}
+
+/// Direction and elongation of a line or an ellipse, from the principal components of its points.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MajorAxis {
+    /// Angle in radians in `(-π/2, π/2]`, counter-clockwise as seen on screen; 0 is horizontal.
+    pub orientation: f64,
+    /// See [`PrincipalComponents::elongation`].
+    pub elongation: f64,
+}
+
+/// An ellipse whose axes are the principal axes of a stroke, see [`PrincipalComponents::fit_ellipse`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AxisEllipse {
+    pub centre: Vector2F,
+    /// Radii along the major and the minor axis.
+    pub radii: Vector2F,
+}
+
+impl AxisEllipse {
+    /// The smaller radius divided by the larger one: 1 for circles, towards 0 for flat ellipses.
+    pub fn aspect(&self) -> f64 {
+        self.radii.x.min(self.radii.y) / self.radii.x.max(self.radii.y)
+    }
+}
+
+impl PrincipalComponents {
+    /// Projects `point` onto the major and minor axis, relative to the centre.
+    pub fn project(&self, point: Vector2F) -> Vector2F {
+        let relative: Vector2F = point - self.centre;
+        Vector2F::new(relative.dot(self.axes[0]), relative.dot(self.axes[1]))
+    }
+
+    pub fn major_axis(&self) -> MajorAxis {
+        // The orientation is measured with y pointing down.
+        let orientation: f64 = if self.orientation == -FRAC_PI_2 || self.orientation == FRAC_PI_2 { FRAC_PI_2 } else { -self.orientation };
+        MajorAxis { orientation, elongation: self.elongation }
+    }
+
+    /// The ellipse along the principal axes that `points` fit best, by least squares on its equation
+    /// `a·u² + b·v² + c·u + d·v = 1`. Unlike the variances, this does not depend on where the points
+    /// are dense, as they are where the hand slowed down. `None` if the points lie on no ellipse.
+    pub fn fit_ellipse(&self, points: &[Vector2F]) -> Option<AxisEllipse> {
+        // Scaled by the spread along the major axis, which keeps the equations well-conditioned.
+        let scale: f64 = self.eigenvalues[0].sqrt();
+        if scale <= 0.0 || !scale.is_finite() {
+            return None;
+        }
+        let mut normal: [[f64; 5]; 4] = [[0.0; 5]; 4];
+        for point in points {
+            let projected: Vector2F = self.project(*point) / scale;
+            let row: [f64; 4] = [projected.x * projected.x, projected.y * projected.y, projected.x, projected.y];
+            for i in 0..4 {
+                for j in 0..4 {
+                    normal[i][j] += row[i] * row[j];
+                }
+                normal[i][4] += row[i];
+            }
+        }
+        let [a, b, c, d] = solve(normal)?;
+        let constant: f64 = 1.0 + c * c / (4.0 * a) + d * d / (4.0 * b);
+        if a <= 0.0 || b <= 0.0 || constant <= 0.0 {
+            return None;
+        }
+        let offset: Vector2F = Vector2F::new(-c / (2.0 * a), -d / (2.0 * b)) * scale;
+        Some(AxisEllipse { centre: self.centre + self.axes[0] * offset.x + self.axes[1] * offset.y, radii: Vector2F::new((constant / a).sqrt(), (constant / b).sqrt()) * scale })
+    }
+}
+
+/// Solves the linear equations with the augmented matrix `rows` (Gaussian elimination with
+/// partial pivoting); `None` if they have no single solution.
+fn solve(mut rows: [[f64; 5]; 4]) -> Option<[f64; 4]> {
+    for column in 0..4 {
+        let pivot: usize = (column..4).max_by(|&a, &b| rows[a][column].abs().total_cmp(&rows[b][column].abs()))?;
+        if rows[pivot][column].abs() < 1e-12 {
+            return None;
+        }
+        rows.swap(column, pivot);
+        let pivot_row: [f64; 5] = rows[column];
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i == column {
+                continue;
+            }
+            let factor: f64 = row[column] / pivot_row[column];
+            for (value, pivot) in row.iter_mut().zip(pivot_row).skip(column) {
+                *value -= factor * pivot;
+            }
+        }
+    }
+    let solution: [f64; 4] = std::array::from_fn(|i| rows[i][4] / rows[i][i]);
+    solution.iter().all(|value| value.is_finite()).then_some(solution)
+}
+
+impl Shape {
+    /// The ellipse along the principal axes of the stroke, see [`PrincipalComponents::fit_ellipse`].
+    pub fn axis_ellipse(&self) -> Option<AxisEllipse> {
+        self.principal_components().fit_ellipse(&self.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect::<Vec<Vector2F>>())
+    }
+
+    pub fn principal_components(&self) -> PrincipalComponents {
+        let centre: Vector2F = if self.coordinates.is_empty() { Vector2F::default() } else { self.find_centre() };
+        let mut covariance: [f64; 3] = [0.0; 3];
+        for coordinate in &self.coordinates {
+            let relative: Vector2F = coordinate.to_f64() - centre;
+            covariance[0] += relative.x * relative.x;
+            covariance[1] += relative.x * relative.y;
+            covariance[2] += relative.y * relative.y;
+        }
+        if !self.coordinates.is_empty() {
+            for value in &mut covariance {
+                *value /= self.coordinates.len() as f64;
+            }
+        }
+        let [xx, xy, yy] = covariance;
+        let mean: f64 = (xx + yy) / 2.0;
+        let spread: f64 = ((xx - yy) / 2.0).hypot(xy);
+        let eigenvalues: [f64; 2] = [mean + spread, (mean - spread).max(0.0)];
+        let orientation: f64 = if spread == 0.0 { 0.0 } else { 0.5 * (2.0 * xy).atan2(xx - yy) };
+        let major: Vector2F = Vector2F::new(orientation.cos(), orientation.sin());
+        let minor: Vector2F = Vector2F::new(-major.y, major.x);
+        let elongation: f64 = if eigenvalues[0] > 0.0 { 1.0 - (eigenvalues[1] / eigenvalues[0]).sqrt() } else { 0.0 };
+        PrincipalComponents { centre, covariance, axes: [major, minor], eigenvalues, elongation, orientation }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::ShapeName;
+    use crate::vector::Vector2D;
+    use std::f64::consts::{FRAC_PI_4, TAU};
+
+    #[test]
+    fn test_principal_components() {
+        let mut coordinates: Vec<Vector2D> = Vec::new();
+        for i in 0..64 {
+            let angle: f64 = TAU * i as f64 / 64.0;
+            let point: Vector2F = Vector2F::new(200.0 * angle.cos(), 50.0 * angle.sin()).rotate(FRAC_PI_4);
+            coordinates.push((point + Vector2F::new(500.0, 500.0)).round());
+        }
+        let shape: Shape = Shape { coordinates, shape_type: ShapeName::Undefined };
+        let components: PrincipalComponents = shape.principal_components();
+        assert!((components.orientation - FRAC_PI_4).abs() < 0.01);
+        assert!((components.eigenvalues[0] / components.eigenvalues[1] - 16.0).abs() < 0.5);
+        assert!((components.elongation - 0.75).abs() < 0.01);
+        assert!((components.major_axis().orientation + FRAC_PI_4).abs() < 0.01);
+        let ellipse: AxisEllipse = components.fit_ellipse(&shape.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect::<Vec<Vector2F>>()).unwrap();
+        assert!(ellipse.centre.distance(Vector2F::new(500.0, 500.0)) < 1.0 && (ellipse.radii.x - 200.0).abs() < 1.0 && (ellipse.radii.y - 50.0).abs() < 1.0, "{:?}", ellipse);
+        assert!((ellipse.aspect() - 0.25).abs() < 0.01 && shape.axis_ellipse() == Some(ellipse));
+    }
+}
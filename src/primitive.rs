@@ -0,0 +1,94 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+
+use crate::pca::{AxisEllipse, PrincipalComponents};
+use crate::shape::{Shape, ShapeName};
+use crate::vector::Vector2F;
+
+/// An ideal geometric shape fitted to a stroke, in screen coordinates.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Primitive {
+    Circle { centre: Vector2F, radius: f64 },
+    /// `radii` holds the semi-major and the semi-minor axis; `rotation` is the angle of the
+    /// major axis in radians.
+    Ellipse { centre: Vector2F, radii: Vector2F, rotation: f64 },
+    Line { start: Vector2F, end: Vector2F },
+    /// A regular star with `points` tips at `radius` around `centre`; `rotation` is the angle of the first tip.
+    Star { centre: Vector2F, radius: f64, points: usize, rotation: f64 },
+}
+
+impl Primitive {
+    /// Points along the primitive; curves are approximated with `segments` line segments.
+    pub fn outline(&self, segments: usize) -> Vec<Vector2F> {
+        let ellipse = |centre: Vector2F, radii: Vector2F, rotation: f64| -> Vec<Vector2F> {
+            (0..=segments).map(|i| {
+                let angle: f64 = std::f64::consts::TAU * i as f64 / segments as f64;
+                centre + Vector2F::new(radii.x * angle.cos(), radii.y * angle.sin()).rotate(rotation)
+            }).collect()
+        };
+        match *self {
+            Primitive::Circle { centre, radius } => ellipse(centre, Vector2F::new(radius, radius), 0.0),
+            Primitive::Ellipse { centre, radii, rotation } => ellipse(centre, radii, rotation),
+            Primitive::Line { start, end } => vec![start, end],
+            // The outline of the regular star polygon that connects every second tip.
+            Primitive::Star { centre, radius, points, rotation } => {
+                let step: f64 = std::f64::consts::PI / points as f64;
+                let inner: f64 = radius * (2.0 * step).cos() / step.cos();
+                (0..=2 * points).map(|i| centre + Vector2F::new(if i % 2 == 0 { radius } else { inner }, 0.0).rotate(rotation + step * i as f64)).collect()
+            },
+        }
+    }
+}
+
+impl fmt::Display for Primitive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Primitive::Circle { centre, radius } => write!(f, "circle at ({:.0}, {:.0}), radius {:.0} px", centre.x, centre.y, radius),
+            Primitive::Ellipse { centre, radii, rotation } => write!(f, "ellipse at ({:.0}, {:.0}), radii {:.0} x {:.0} px, rotated {:.0}°", centre.x, centre.y, radii.x, radii.y, rotation.to_degrees()),
+            Primitive::Line { start, end } => write!(f, "line from ({:.0}, {:.0}) to ({:.0}, {:.0}), {:.0} px long", start.x, start.y, end.x, end.y, start.distance(*end)),
+            Primitive::Star { centre, radius, points, .. } => write!(f, "star at ({:.0}, {:.0}) with {} points, radius {:.0} px", centre.x, centre.y, points, radius),
+        }
+    }
+}
+
+impl Shape {
+    /// Fits the primitive for `shape_name` to the stroke; `None` for names without a primitive.
+    pub fn fit_primitive(&self, shape_name: &ShapeName) -> Option<Primitive> {
+        if self.coordinates.is_empty() {
+            return None;
+        }
+        match shape_name {
+            ShapeName::Circle => {
+                let centre: Vector2F = self.find_centre();
+                Some(Primitive::Circle { centre, radius: self.get_point_distances(centre).avg })
+            },
+            ShapeName::Ellipse => {
+                let components: PrincipalComponents = self.principal_components();
+                let points: Vec<Vector2F> = self.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+                // Points that lie on no ellipse still get the one with their spread: the points of an
+                // ellipse sampled evenly by angle have a variance of r²/2 along each axis.
+                let ellipse: AxisEllipse = components.fit_ellipse(&points).unwrap_or(AxisEllipse { centre: components.centre, radii: Vector2F::new((2.0 * components.eigenvalues[0]).sqrt(), (2.0 * components.eigenvalues[1]).sqrt()) });
+                Some(Primitive::Ellipse { centre: ellipse.centre, radii: ellipse.radii, rotation: components.orientation })
+            },
+            ShapeName::Line => Some(Primitive::Line { start: self.coordinates[0].to_f64(), end: self.coordinates[self.coordinates.len()-1].to_f64() }),
+            ShapeName::Arrow => self.arrow().map(|arrow| Primitive::Line { start: arrow.tail, end: arrow.tip }),
+            ShapeName::Star => self.star().map(|star| Primitive::Star { centre: star.centre, radius: star.radius, points: star.points, rotation: star.rotation }),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,297 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::io::Cursor;
+
+use crate::distances::farthest_pair;
+use crate::shape::{Shape, ShapeName};
+use crate::vector::{Vector2D, Vector2F};
+
+const BACKGROUND_COLOUR: [u8; 3] = [255, 255, 255];
+const STROKE_COLOUR: [u8; 3] = [31, 58, 147];
+const PRIMITIVE_COLOUR: [u8; 3] = [211, 84, 0];
+/// Pixels darker than this (0-255 luma) count as ink when reading an image.
+const INK_THRESHOLD: f64 = 128.0;
+
+#[derive(Debug)]
+pub enum RasterError {
+    Decode(png::DecodingError),
+    Encode(png::EncodingError),
+    /// The image does not contain any dark pixels.
+    NoInk,
+    /// The canvas has more pixels than can be allocated.
+    TooLarge { width: u32, height: u32 },
+}
+
+impl fmt::Display for RasterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RasterError::Decode(error) => write!(f, "could not decode PNG: {}", error),
+            RasterError::Encode(error) => write!(f, "could not encode PNG: {}", error),
+            RasterError::NoInk => write!(f, "the image does not contain a drawing"),
+            RasterError::TooLarge { width, height } => write!(f, "a canvas of {}x{} pixels is too large", width, height),
+        }
+    }
+}
+
+impl Error for RasterError {}
+
+/// An RGB image with 8 bits per channel.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Canvas {
+    /// Fails if the memory for the pixels cannot be allocated.
+    pub fn new(width: u32, height: u32, colour: [u8; 3]) -> Result<Self, RasterError> {
+        let too_large = || RasterError::TooLarge { width, height };
+        let bytes: usize = (width as usize).checked_mul(height as usize).and_then(|pixels| pixels.checked_mul(colour.len())).ok_or_else(too_large)?;
+        let mut pixels: Vec<u8> = Vec::new();
+        pixels.try_reserve_exact(bytes).map_err(|_| too_large())?;
+        pixels.extend(colour.iter().cycle().take(bytes));
+        Ok(Canvas { width, height, pixels })
+    }
+
+    /// Blends `colour` into the pixel; `coverage` goes from 0 (unchanged) to 1 (replaced).
+    fn blend(&mut self, x: i64, y: i64, colour: [u8; 3], coverage: f64) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let offset: usize = (y as usize * self.width as usize + x as usize) * 3;
+        for (channel, value) in colour.iter().enumerate() {
+            let old: f64 = self.pixels[offset + channel] as f64;
+            self.pixels[offset + channel] = (old + (*value as f64 - old) * coverage.clamp(0.0, 1.0)).round() as u8;
+        }
+    }
+
+    /// Draws an anti-aliased segment with round caps.
+    pub fn draw_segment(&mut self, start: Vector2F, end: Vector2F, width: f64, colour: [u8; 3]) {
+        let radius: f64 = width / 2.0;
+        let (min_x, max_x) = ((start.x.min(end.x) - radius - 1.0).floor() as i64, (start.x.max(end.x) + radius + 1.0).ceil() as i64);
+        let (min_y, max_y) = ((start.y.min(end.y) - radius - 1.0).floor() as i64, (start.y.max(end.y) + radius + 1.0).ceil() as i64);
+        let direction: Vector2F = end - start;
+        let length_squared: f64 = direction.dot(direction);
+        for y in min_y.max(0)..=max_y.min(self.height as i64 - 1) {
+            for x in min_x.max(0)..=max_x.min(self.width as i64 - 1) {
+                let pixel: Vector2F = Vector2F::new(x as f64 + 0.5, y as f64 + 0.5);
+                let t: f64 = if length_squared > 0.0 { ((pixel - start).dot(direction) / length_squared).clamp(0.0, 1.0) } else { 0.0 };
+                let distance: f64 = pixel.distance(start + direction * t);
+                self.blend(x, y, colour, radius + 0.5 - distance);
+            }
+        }
+    }
+
+    pub fn draw_polyline(&mut self, points: &[Vector2F], width: f64, colour: [u8; 3]) {
+        for pair in points.windows(2) {
+            self.draw_segment(pair[0], pair[1], width, colour);
+        }
+        if points.len() == 1 {
+            self.draw_segment(points[0], points[0], width, colour);
+        }
+    }
+
+    pub fn encode_png(&self) -> Result<Vec<u8>, RasterError> {
+        let mut bytes: Vec<u8> = Vec::new();
+        let mut encoder: png::Encoder<&mut Vec<u8>> = png::Encoder::new(&mut bytes, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(RasterError::Encode)?;
+        writer.write_image_data(&self.pixels).map_err(RasterError::Encode)?;
+        writer.finish().map_err(RasterError::Encode)?;
+        Ok(bytes)
+    }
+}
+
+/// Renders the stroke, and the primitive fitted for `shape_name`, to a square PNG thumbnail.
+pub fn render_png(shape: &Shape, shape_name: &ShapeName, size: u32) -> Result<Vec<u8>, RasterError> {
+    let mut canvas: Canvas = Canvas::new(size, size, BACKGROUND_COLOUR)?;
+    let points: Vec<Vector2F> = shape.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+    if !points.is_empty() {
+        let mut min: Vector2F = Vector2F::new(f64::MAX, f64::MAX);
+        let mut max: Vector2F = Vector2F::new(f64::MIN, f64::MIN);
+        for point in &points {
+            min = Vector2F::new(min.x.min(point.x), min.y.min(point.y));
+            max = Vector2F::new(max.x.max(point.x), max.y.max(point.y));
+        }
+        let extent: f64 = (max.x - min.x).max(max.y - min.y).max(1.0);
+        let scale: f64 = size as f64 * 0.84 / extent;
+        let middle: Vector2F = (min + max) / 2.0;
+        let to_canvas = |point: Vector2F| (point - middle) * scale + Vector2F::new(size as f64 / 2.0, size as f64 / 2.0);
+        let width: f64 = (size as f64 / 64.0).max(1.0);
+        canvas.draw_polyline(&points.iter().map(|point| to_canvas(*point)).collect::<Vec<Vector2F>>(), width, STROKE_COLOUR);
+        if let Some(primitive) = shape.fit_primitive(shape_name) {
+            let outline: Vec<Vector2F> = primitive.outline(64).into_iter().map(to_canvas).collect();
+            canvas.draw_polyline(&outline, (width / 2.0).max(1.0), PRIMITIVE_COLOUR);
+        }
+    }
+    canvas.encode_png()
+}
+
+/// Reads a dark-on-light PNG and returns the outline of the largest drawn figure.
+/// For closed figures (with a hole, like a drawn circle) this is the full outer contour;
+/// for open ones (like a line) only one side of the contour between its two farthest points
+/// is returned, so the result follows the drawn stroke.
+pub fn contour_from_png(bytes: &[u8]) -> Result<Vec<Vector2D>, RasterError> {
+    let mut decoder: png::Decoder<Cursor<&[u8]>> = png::Decoder::new(Cursor::new(bytes));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(RasterError::Decode)?;
+    let mut buffer: Vec<u8> = vec![0; reader.output_buffer_size()];
+    let info: png::OutputInfo = reader.next_frame(&mut buffer).map_err(RasterError::Decode)?;
+    let channels: usize = info.color_type.samples();
+    let (width, height) = (info.width as usize, info.height as usize);
+    let mut ink: Vec<bool> = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let row: &[u8] = &buffer[y * info.line_size..];
+        for x in 0..width {
+            let pixel: &[u8] = &row[x * channels..(x + 1) * channels];
+            let (luma, alpha) = match pixel {
+                [grey] => (*grey as f64, 255.0),
+                [grey, alpha] => (*grey as f64, *alpha as f64),
+                [r, g, b] => (0.299 * *r as f64 + 0.587 * *g as f64 + 0.114 * *b as f64, 255.0),
+                [r, g, b, alpha, ..] => (0.299 * *r as f64 + 0.587 * *g as f64 + 0.114 * *b as f64, *alpha as f64),
+                [] => (255.0, 255.0),
+            };
+            // Transparent pixels are treated as if the image was drawn on white paper.
+            ink.push(255.0 - (255.0 - luma) * alpha / 255.0 < INK_THRESHOLD);
+        }
+    }
+    trace_contour(&ink, width, height).ok_or(RasterError::NoInk)
+}
+
+/// Offsets of the 8 neighbours, clockwise on screen starting west.
+const NEIGHBOURS: [(i64, i64); 8] = [(-1, 0), (-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1)];
+
+fn trace_contour(ink: &[bool], width: usize, height: usize) -> Option<Vec<Vector2D>> {
+    let at = |x: i64, y: i64| -> Option<usize> {
+        (x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height).then(|| y as usize * width + x as usize)
+    };
+    // Largest 8-connected component of ink.
+    let mut label: Vec<usize> = vec![0; ink.len()];
+    let (mut best_label, mut best_size, mut best_start) = (0, 0, 0);
+    let mut next_label: usize = 1;
+    for start in 0..ink.len() {
+        if !ink[start] || label[start] != 0 {
+            continue;
+        }
+        let mut queue: VecDeque<usize> = VecDeque::from([start]);
+        label[start] = next_label;
+        let mut size: usize = 0;
+        while let Some(pixel) = queue.pop_front() {
+            size += 1;
+            let (x, y) = ((pixel % width) as i64, (pixel / width) as i64);
+            for (dx, dy) in NEIGHBOURS {
+                if let Some(neighbour) = at(x + dx, y + dy) {
+                    if ink[neighbour] && label[neighbour] == 0 {
+                        label[neighbour] = next_label;
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+        }
+        if size > best_size {
+            (best_label, best_size, best_start) = (next_label, size, start);
+        }
+        next_label += 1;
+    }
+    if best_size == 0 {
+        return None;
+    }
+    let figure = |x: i64, y: i64| at(x, y).is_some_and(|pixel| label[pixel] == best_label);
+
+    // Moore neighbour tracing from the first pixel in reading order, whose west neighbour is empty.
+    let start: (i64, i64) = ((best_start % width) as i64, (best_start / width) as i64);
+    let mut contour: Vec<(i64, i64)> = vec![start];
+    let (mut current, mut backtrack) = (start, 0);
+    let mut second: Option<(i64, i64)> = None;
+    for _ in 0..4 * best_size + 8 {
+        let next = (1..=8).map(|step| (backtrack + step) % 8).find(|&direction| figure(current.0 + NEIGHBOURS[direction].0, current.1 + NEIGHBOURS[direction].1));
+        let Some(direction) = next else { break };
+        let pixel: (i64, i64) = (current.0 + NEIGHBOURS[direction].0, current.1 + NEIGHBOURS[direction].1);
+        if current == start && second == Some(pixel) {
+            contour.pop();
+            break;
+        }
+        second.get_or_insert(pixel);
+        // The neighbour checked just before the found pixel is empty; seen from the found pixel
+        // it lies two steps (straight move) or three steps (diagonal move) back.
+        backtrack = if direction.is_multiple_of(2) { (direction + 6) % 8 } else { (direction + 5) % 8 };
+        current = pixel;
+        contour.push(pixel);
+    }
+    let contour: Vec<Vector2D> = contour.into_iter().map(|(x, y)| Vector2D { x: x as i32, y: y as i32 }).collect();
+
+    // A figure is closed if some background pixel cannot be reached from the image border.
+    let mut outside: Vec<bool> = vec![false; ink.len()];
+    let mut queue: VecDeque<(i64, i64)> = VecDeque::new();
+    for x in -1..=width as i64 {
+        queue.extend([(x, -1), (x, height as i64)]);
+    }
+    for y in 0..height as i64 {
+        queue.extend([(-1, y), (width as i64, y)]);
+    }
+    while let Some((x, y)) = queue.pop_front() {
+        for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            if let Some(neighbour) = at(x + dx, y + dy) {
+                if !outside[neighbour] && label[neighbour] != best_label {
+                    outside[neighbour] = true;
+                    queue.push_back((x + dx, y + dy));
+                }
+            }
+        }
+    }
+    let closed: bool = (0..ink.len()).any(|pixel| label[pixel] != best_label && !outside[pixel]);
+    if closed {
+        return Some(contour);
+    }
+    let shape: Shape = Shape { coordinates: contour, shape_type: ShapeName::Undefined };
+    match farthest_pair(&shape.coordinates, &shape.convex_hull()) {
+        Some((a, b)) => Some(shape.coordinates[a..=b].to_vec()),
+        None => Some(shape.coordinates),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_round_trip() {
+        let mut circle: Canvas = Canvas::new(200, 200, BACKGROUND_COLOUR).unwrap();
+        let points: Vec<Vector2F> = (0..=48).map(|i| {
+            let angle: f64 = std::f64::consts::TAU * i as f64 / 48.0;
+            Vector2F::new(100.0 + 70.0 * angle.cos(), 100.0 + 70.0 * angle.sin())
+        }).collect();
+        circle.draw_polyline(&points, 5.0, [0, 0, 0]);
+        let contour: Vec<Vector2D> = contour_from_png(&circle.encode_png().unwrap()).unwrap();
+        assert_eq!(Shape { coordinates: contour, shape_type: ShapeName::Undefined }.classify().shape_name, ShapeName::Circle);
+
+        let mut line: Canvas = Canvas::new(200, 100, BACKGROUND_COLOUR).unwrap();
+        line.draw_segment(Vector2F::new(20.0, 30.0), Vector2F::new(180.0, 70.0), 4.0, [0, 0, 0]);
+        let contour: Vec<Vector2D> = contour_from_png(&line.encode_png().unwrap()).unwrap();
+        assert_eq!(Shape { coordinates: contour.clone(), shape_type: ShapeName::Undefined }.classify().shape_name, ShapeName::Line);
+        let thumbnail: Vec<u8> = render_png(&Shape { coordinates: contour, shape_type: ShapeName::Undefined }, &ShapeName::Line, 64).unwrap();
+        assert_eq!(&thumbnail[1..4], b"PNG");
+        assert!(matches!(Canvas::new(u32::MAX, u32::MAX, BACKGROUND_COLOUR), Err(RasterError::TooLarge { .. })));
+        assert!(matches!(render_png(&Shape { coordinates: Vec::new(), shape_type: ShapeName::Undefined }, &ShapeName::Line, u32::MAX), Err(RasterError::TooLarge { .. })));
+        assert!(matches!(contour_from_png(&Canvas::new(10, 10, BACKGROUND_COLOUR).unwrap().encode_png().unwrap()), Err(RasterError::NoInk)));
+    }
+}
@@ -0,0 +1,430 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Recognizers for single shapes, and the classifier that asks all of them.
+//!
+//! Every recognizer scores the stroke as its own shape. The classifier accepts the candidates that
+//! reach the threshold of their recognizer and picks the one with the highest priority, or the
+//! highest score if several share it. If none is accepted, the stroke is `Unknown` and the candidate
+//! with the highest score is reported as the closest guess. Strokes with too few points, a length
+//! of 0, or that are too small, too fast or too slow are rejected before any recognizer sees them,
+//! see [`Limits`].
+//!
+//! Other crates can add their own shapes by implementing [`ShapeRecognizer`] and registering it:
+//!
+//! ```
+//! use movement::recognizer::{Classifier, ShapeRecognizer};
+//! use movement::{Calibration, Classification, Shape, ShapeName};
+//!
+//! struct Dot;
+//!
+//! impl ShapeRecognizer for Dot {
+//!     fn recognize(&self, shape: &Shape) -> Option<Classification> {
+//!         let size: f64 = shape.bounding_box().width().max(shape.bounding_box().height());
+//!         let name: ShapeName = ShapeName::Custom(String::from("dot"));
+//!         Some(Classification::new(name.clone(), name, if size <= 5.0 { 100.0 } else { 0.0 }))
+//!     }
+//!
+//!     fn priority(&self) -> i32 {
+//!         100
+//!     }
+//!
+//!     fn calibration(&self) -> Calibration {
+//!         Calibration { midpoint: 50.0, slope: 0.2 }
+//!     }
+//! }
+//!
+//! let mut classifier: Classifier = Classifier::default();
+//! classifier.register(Box::new(Dot));
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::calibration::Calibration;
+use crate::hull::{BoundingRectangle, HullDescriptors};
+use crate::limits::Limits;
+use crate::shape::{Classification, Shape, ShapeName};
+use crate::{CIRCLE_MAX_CORNERS, CIRCLE_MIN_ASPECT, TOLERANCE_GENERAL};
+
+pub trait ShapeRecognizer {
+    /// The stroke classified as the shape of this recognizer, with `shape_name` and `candidate` both
+    /// set to it and a score in percent; `None` if the shape does not apply to the stroke at all.
+    fn recognize(&self, shape: &Shape) -> Option<Classification>;
+
+    /// Of several accepted candidates, the one with the highest priority wins.
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    /// Smallest score at which the candidate is accepted.
+    fn threshold(&self) -> f64 {
+        100.0 - 100.0 * TOLERANCE_GENERAL
+    }
+
+    /// How the score translates into the probability that the stroke is the shape, best fitted on
+    /// labelled strokes with [`Calibration::fit`].
+    fn calibration(&self) -> Calibration;
+}
+
+// Fitted on the labelled corpus of generated strokes in the tests, where a recognizer that does not
+// apply to a stroke counts as a score of 0; `test_calibrations` fits them again.
+const CATALOGUE_CALIBRATION: Calibration = Calibration { midpoint: 84.7, slope: 0.111 };
+const CIRCLE_CALIBRATION: Calibration = Calibration { midpoint: 62.8, slope: 0.123 };
+const ARROW_CALIBRATION: Calibration = Calibration { midpoint: 59.2, slope: 0.129 };
+const LINE_CALIBRATION: Calibration = Calibration { midpoint: 64.9, slope: 0.126 };
+const STAR_CALIBRATION: Calibration = Calibration { midpoint: 60.6, slope: 0.127 };
+const ANNOTATION_CALIBRATION: Calibration = Calibration { midpoint: 97.0, slope: 0.079 };
+const ELLIPSE_CALIBRATION: Calibration = Calibration { midpoint: 85.6, slope: 0.196 };
+
+/// A recognizer with a different calibration, for example one fitted with [`Calibration::fit`].
+pub struct Calibrated {
+    pub recognizer: Box<dyn ShapeRecognizer>,
+    pub calibration: Calibration,
+}
+
+/// One of the shapes a stroke might be, see [`Classifier::rank`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Hypothesis {
+    pub classification: Classification,
+    /// Between 0 and 1; the probabilities of all hypotheses for a stroke add up to 1.
+    pub probability: f64,
+}
+
+/// Hearts, diamonds and the other shapes of the [catalogue](crate::catalogue). Priority 60, since
+/// some of them are round enough to pass as circles.
+pub struct CatalogueRecognizer;
+/// Applies only to nearly closed strokes with at most `CIRCLE_MAX_CORNERS` corners that are rounder
+/// than `CIRCLE_MIN_ASPECT`. Priority 50.
+pub struct CircleRecognizer;
+/// Priority 40, so that an arrow with a small head is not taken for a line.
+pub struct ArrowRecognizer;
+/// Applies only to thin strokes whose ends lie at both short sides of their minimum bounding
+/// rectangle. Priority 30.
+pub struct LineRecognizer;
+/// Priority 20.
+pub struct StarRecognizer;
+/// Check marks, crosses, zig-zags and carets. Priority 10.
+pub struct AnnotationRecognizer;
+/// Applies only to nearly closed strokes that fill their convex hull and are flatter than
+/// `CIRCLE_MIN_ASPECT`. Priority 0.
+pub struct EllipseRecognizer;
+
+impl ShapeRecognizer for CatalogueRecognizer {
+    fn recognize(&self, shape: &Shape) -> Option<Classification> {
+        let (shape_name, score) = shape.closed_shape()?;
+        Some(Classification { winding: shape.winding(), ..Classification::new(shape_name.clone(), shape_name, score) })
+    }
+
+    fn priority(&self) -> i32 {
+        60
+    }
+
+    fn calibration(&self) -> Calibration {
+        CATALOGUE_CALIBRATION
+    }
+}
+
+impl ShapeRecognizer for CircleRecognizer {
+    fn recognize(&self, shape: &Shape) -> Option<Classification> {
+        // Flat ones are ellipses, and polygons, whose corners lie on a circle as well, have corners.
+        let (corners, _) = shape.closed_corners()?;
+        if corners.len() > CIRCLE_MAX_CORNERS || shape.axis_ellipse().is_some_and(|ellipse| ellipse.aspect() < CIRCLE_MIN_ASPECT) {
+            return None;
+        }
+        let score: f64 = shape.get_point_distances(shape.find_centre()).passes_percent;
+        Some(Classification { winding: shape.winding(), ..Classification::new(ShapeName::Circle, ShapeName::Circle, score) })
+    }
+
+    fn priority(&self) -> i32 {
+        50
+    }
+
+    fn calibration(&self) -> Calibration {
+        CIRCLE_CALIBRATION
+    }
+}
+
+impl ShapeRecognizer for ArrowRecognizer {
+    fn recognize(&self, shape: &Shape) -> Option<Classification> {
+        let (arrow, score) = shape.arrow_with_score()?;
+        Some(Classification { swipe: Some(arrow.swipe), arrow: Some(arrow), ..Classification::new(ShapeName::Arrow, ShapeName::Arrow, score) })
+    }
+
+    fn priority(&self) -> i32 {
+        40
+    }
+
+    fn calibration(&self) -> Calibration {
+        ARROW_CALIBRATION
+    }
+}
+
+impl ShapeRecognizer for LineRecognizer {
+    fn recognize(&self, shape: &Shape) -> Option<Classification> {
+        let (start, end) = (shape.coordinates.first()?.to_f64(), shape.coordinates.last()?.to_f64());
+        // A thin stroke whose ends lie at the two short sides of its minimum bounding rectangle.
+        let rectangle: BoundingRectangle = shape.hull_descriptors().bounding_rectangle;
+        let (length, thickness) = (rectangle.width.max(rectangle.height), rectangle.width.min(rectangle.height));
+        if thickness > TOLERANCE_GENERAL * length || start.distance(end) < (1.0 - TOLERANCE_GENERAL) * length {
+            return None;
+        }
+        Some(Classification { swipe: shape.swipe(), major_axis: Some(shape.principal_components().major_axis()), ..Classification::new(ShapeName::Line, ShapeName::Line, shape.line_score()) })
+    }
+
+    fn priority(&self) -> i32 {
+        30
+    }
+
+    fn calibration(&self) -> Calibration {
+        LINE_CALIBRATION
+    }
+}
+
+impl ShapeRecognizer for StarRecognizer {
+    fn recognize(&self, shape: &Shape) -> Option<Classification> {
+        let (star, score) = shape.star_with_score()?;
+        Some(Classification { winding: shape.winding(), star: Some(star), ..Classification::new(ShapeName::Star, ShapeName::Star, score) })
+    }
+
+    fn priority(&self) -> i32 {
+        20
+    }
+
+    fn calibration(&self) -> Calibration {
+        STAR_CALIBRATION
+    }
+}
+
+impl ShapeRecognizer for AnnotationRecognizer {
+    fn recognize(&self, shape: &Shape) -> Option<Classification> {
+        let (shape_name, score) = shape.annotation()?;
+        Some(Classification { bounding_box: Some(shape.bounding_box()), ..Classification::new(shape_name.clone(), shape_name, score) })
+    }
+
+    fn priority(&self) -> i32 {
+        10
+    }
+
+    fn calibration(&self) -> Calibration {
+        ANNOTATION_CALIBRATION
+    }
+}
+
+impl ShapeRecognizer for EllipseRecognizer {
+    fn recognize(&self, shape: &Shape) -> Option<Classification> {
+        // Nearly closed, and filling its convex hull, which dented strokes do not.
+        let (start, end) = (shape.coordinates.first()?.to_f64(), shape.coordinates.last()?.to_f64());
+        let descriptors: HullDescriptors = shape.hull_descriptors();
+        if start.distance(end) > TOLERANCE_GENERAL * descriptors.perimeter || descriptors.solidity < 1.0 - TOLERANCE_GENERAL {
+            return None;
+        }
+        // Round ones are circles.
+        if shape.axis_ellipse().is_some_and(|ellipse| ellipse.aspect() >= CIRCLE_MIN_ASPECT) {
+            return None;
+        }
+        let score: f64 = shape.ellipse_score()?;
+        Some(Classification { winding: shape.winding(), major_axis: Some(shape.principal_components().major_axis()), ..Classification::new(ShapeName::Ellipse, ShapeName::Ellipse, score) })
+    }
+
+    fn calibration(&self) -> Calibration {
+        ELLIPSE_CALIBRATION
+    }
+}
+
+impl ShapeRecognizer for Calibrated {
+    fn recognize(&self, shape: &Shape) -> Option<Classification> {
+        self.recognizer.recognize(shape)
+    }
+
+    fn priority(&self) -> i32 {
+        self.recognizer.priority()
+    }
+
+    fn threshold(&self) -> f64 {
+        self.recognizer.threshold()
+    }
+
+    fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+}
+
+/// An ordered list of recognizers. [`Classifier::default`] has the built-in ones.
+pub struct Classifier {
+    pub recognizers: Vec<Box<dyn ShapeRecognizer>>,
+    /// Strokes breaking these are rejected before any recognizer sees them.
+    pub limits: Limits,
+}
+
+impl Default for Classifier {
+    fn default() -> Self {
+        Classifier { recognizers: vec![Box::new(CatalogueRecognizer), Box::new(CircleRecognizer), Box::new(ArrowRecognizer), Box::new(LineRecognizer), Box::new(StarRecognizer), Box::new(AnnotationRecognizer), Box::new(EllipseRecognizer)], limits: Limits::default() }
+    }
+}
+
+impl Classifier {
+    pub fn register(&mut self, recognizer: Box<dyn ShapeRecognizer>) {
+        self.recognizers.push(recognizer);
+    }
+
+    pub fn classify(&self, shape: &Shape) -> Classification {
+        self.classify_timed(shape, None)
+    }
+
+    /// Like [`Classifier::classify`], but also rejects strokes drawn too fast or too slowly
+    /// if `duration`, the time it took to draw the stroke, is known.
+    pub fn classify_timed(&self, shape: &Shape, duration: Option<Duration>) -> Classification {
+        if let Some(rejection) = self.limits.check(shape, duration) {
+            return Classification { rejection: Some(rejection), ..Classification::new(ShapeName::Unknown, ShapeName::Unknown, 0.0) };
+        }
+        let mut accepted: Option<(i32, Classification)> = None;
+        let mut closest: Option<Classification> = None;
+        for recognizer in &self.recognizers {
+            let Some(classification) = recognizer.recognize(shape).filter(|classification| classification.score.is_finite()) else {
+                continue;
+            };
+            if classification.score >= recognizer.threshold() {
+                let priority: i32 = recognizer.priority();
+                if accepted.as_ref().is_none_or(|(best, best_classification)| (priority, classification.score) > (*best, best_classification.score)) {
+                    accepted = Some((priority, classification));
+                }
+            } else if closest.as_ref().is_none_or(|best| classification.score > best.score) {
+                closest = Some(classification);
+            }
+        }
+        match (accepted, closest) {
+            (Some((_, classification)), _) => classification,
+            (None, Some(closest)) => Classification::new(ShapeName::Unknown, closest.candidate, closest.score),
+            (None, None) => Classification::new(ShapeName::Unknown, ShapeName::Unknown, 0.0),
+        }
+    }
+
+    /// Every shape the stroke might be, most probable first; `Unknown` stands for none of them.
+    /// A rejected stroke is `Unknown` for sure.
+    ///
+    /// The probabilities come from a multinomial logistic model with `Unknown` as the reference
+    /// class, whose logit for a shape is the log-odds its recognizer's calibration gives (a softmax).
+    /// Every calibration is fitted one against the rest on strokes that are at most one of the
+    /// shapes, and on those only the right recognizer scores high, so the odds against the rest are
+    /// the odds against `Unknown`. If a single recognizer applies, its shape gets exactly the
+    /// calibrated probability.
+    pub fn rank(&self, shape: &Shape) -> Vec<Hypothesis> {
+        self.rank_timed(shape, None)
+    }
+
+    /// Like [`Classifier::rank`], also rejecting strokes drawn too fast or too slow, like
+    /// [`Classifier::classify_timed`].
+    pub fn rank_timed(&self, shape: &Shape, duration: Option<Duration>) -> Vec<Hypothesis> {
+        if let Some(rejection) = self.limits.check(shape, duration) {
+            return vec![Hypothesis { classification: Classification { rejection: Some(rejection), ..Classification::new(ShapeName::Unknown, ShapeName::Unknown, 0.0) }, probability: 1.0 }];
+        }
+        let mut logits: Vec<(Classification, f64)> = self.recognizers.iter().filter_map(|recognizer| {
+            recognizer.recognize(shape).map(|classification| (classification.clone(), recognizer.calibration().log_odds(classification.score).clamp(f64::MIN, f64::MAX)))
+        }).collect();
+        logits.push((Classification::new(ShapeName::Unknown, ShapeName::Unknown, 0.0), 0.0));
+        // Subtracting the largest logit keeps the exponentials from overflowing.
+        let largest: f64 = logits.iter().map(|(_, logit)| *logit).fold(0.0, f64::max);
+        let total: f64 = logits.iter().map(|(_, logit)| (logit - largest).exp()).sum();
+        let mut hypotheses: Vec<Hypothesis> = logits.into_iter().map(|(classification, logit)| Hypothesis { classification, probability: (logit - largest).exp() / total }).collect();
+        hypotheses.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+        hypotheses
+    }
+}
+
+impl fmt::Display for Hypothesis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({:.0}%)", self.classification.candidate.to_string().to_lowercase(), self.probability * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pca::MajorAxis;
+    use crate::shape::fixtures::corpus;
+    use crate::synthetic::{Figure, Generator};
+    use crate::vector::{Vector2D, Vector2F};
+    use crate::AMBIGUOUS_PROBABILITY;
+
+    /// Accepts every stroke as a custom shape with a fixed score and priority.
+    struct Fixed(&'static str, f64, i32);
+
+    impl ShapeRecognizer for Fixed {
+        fn recognize(&self, _: &Shape) -> Option<Classification> {
+            let name: ShapeName = ShapeName::Custom(String::from(self.0));
+            Some(Classification::new(name.clone(), name, self.1))
+        }
+
+        fn priority(&self) -> i32 {
+            self.2
+        }
+
+        fn calibration(&self) -> Calibration {
+            Calibration { midpoint: 75.0, slope: 0.2 }
+        }
+    }
+
+    #[test]
+    fn test_classifier() {
+        let circle: Shape = Shape { coordinates: (0..=60).map(|i| (Vector2F::new(500.0, 500.0) + Vector2F::new(200.0, 0.0).rotate(std::f64::consts::TAU * i as f64 / 60.0)).round()).collect(), shape_type: ShapeName::Undefined };
+        assert_eq!(circle.classify().shape_name, ShapeName::Circle);
+
+        let mut classifier: Classifier = Classifier::default();
+        classifier.register(Box::new(Fixed("blob", 90.0, 0)));
+        assert_eq!(classifier.classify(&circle).shape_name, ShapeName::Circle);
+        classifier.register(Box::new(Fixed("ring", 80.0, 100)));
+        let classification: Classification = classifier.classify(&circle);
+        assert_eq!((classification.to_string(), classification.shape_name), (String::from("RING (80%)"), ShapeName::Custom(String::from("ring"))));
+
+        // Without an accepted candidate the best score is reported.
+        let classifier: Classifier = Classifier { recognizers: vec![Box::new(Fixed("low", 30.0, 5)), Box::new(Fixed("higher", 60.0, 0))], limits: Limits::default() };
+        assert_eq!(classifier.classify(&circle).to_string(), "UNKNOWN (60% higher)");
+
+        let hypotheses: Vec<Hypothesis> = classifier.rank(&circle);
+        assert_eq!(hypotheses.iter().map(ToString::to_string).collect::<Vec<String>>(), vec!["unknown (95%)", "higher (5%)", "low (0%)"]);
+        assert!((hypotheses.iter().map(|hypothesis| hypothesis.probability).sum::<f64>() - 1.0).abs() < 1e-9);
+        let classifier: Classifier = Classifier { recognizers: vec![Box::new(Fixed("low", 30.0, 5)), Box::new(Calibrated { recognizer: Box::new(Fixed("higher", 60.0, 0)), calibration: Calibration { midpoint: 50.0, slope: 0.2 } })], limits: Limits::default() };
+        assert_eq!(classifier.rank(&circle)[0].to_string(), "higher (88%)");
+        let best: Hypothesis = circle.rank().remove(0);
+        assert!(best.classification.candidate == ShapeName::Circle && best.probability > AMBIGUOUS_PROBABILITY, "{}", best);
+        // Clean strokes are more probably the drawn shape than anything else together.
+        for (figure, expected) in [(Figure::Circle, ShapeName::Circle), (Figure::Ellipse { aspect: 0.5 }, ShapeName::Ellipse), (Figure::Line, ShapeName::Line)] {
+            let best: Hypothesis = Generator::default().stroke(figure).shape().rank().remove(0);
+            assert!(best.classification.candidate == expected && best.probability > 0.5, "{:?}: {}", figure, best);
+        }
+        let empty: Classifier = Classifier { recognizers: Vec::new(), limits: Limits::default() };
+        assert_eq!(empty.classify(&circle).to_string(), "UNKNOWN (0%)");
+        let ellipse: Classification = Generator { rotation: 0.3, ..Generator::default() }.stroke(Figure::Ellipse { aspect: 0.5 }).shape().classify();
+        let axis: MajorAxis = ellipse.major_axis.unwrap();
+        assert!((axis.orientation + 0.3).abs() < 0.01 && (axis.elongation - 0.5).abs() < 0.01, "{:?}", axis);
+        let dot: Shape = Shape { coordinates: vec![Vector2D { x: 1, y: 1 }], shape_type: ShapeName::Undefined };
+        assert_eq!(Classifier::default().classify(&dot).to_string(), "UNKNOWN (too few points)");
+        assert_eq!(Classifier::default().classify_timed(&circle, Some(Duration::from_millis(10))).rejection, Some(crate::limits::Rejection::TooFast));
+        assert_eq!(dot.rank()[0].probability, 1.0);
+        assert_eq!(Classifier::default().rank_timed(&circle, Some(Duration::from_millis(10)))[0].classification.rejection, Some(crate::limits::Rejection::TooFast));
+    }
+
+    #[test]
+    fn test_calibrations() {
+        let corpus: Vec<(Shape, ShapeName)> = corpus();
+        for recognizer in Classifier::default().recognizers {
+            let samples: Vec<(f64, bool)> = corpus.iter().map(|(shape, label)| recognizer.recognize(shape).map_or((0.0, false), |classification| (classification.score, &classification.candidate == label))).collect();
+            let (fitted, calibration) = (Calibration::fit(&samples).unwrap(), recognizer.calibration());
+            assert!((fitted.midpoint - calibration.midpoint).abs() < 0.1 && (fitted.slope - calibration.slope).abs() < 0.001, "fitted {:?} instead of {:?}", fitted, calibration);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::{Duration, Instant};
+
+use mouse_position::mouse_position::Mouse;
+
+use crate::vector::Vector2D;
+use crate::END_FIGURE_TIMEOUT;
+
+pub fn get_mouse_position() -> Vector2D {
+    let position = Mouse::get_mouse_position();
+    match position {
+        Mouse::Position { x, y } => Vector2D { x, y },
+        Mouse::Error => Vector2D { x: 0, y: 0 },
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Recording {
+    pub coordinates: Vec<Vector2D>,
+    initialized: bool,
+    running: bool,
+    stop_coordinate: Vector2D,
+    coordinate_unchanged_cycles: u8,
+    started: Option<Instant>,
+    last_moved: Option<Instant>,
+}
+
+pub enum RecordingStatus {
+    Waiting,
+    /// The cursor moved for the first time; the recording has its first coordinate.
+    Started,
+    Running,
+    Finished,
+}
+
+impl Recording {
+    /// Time between the first and the last movement of the cursor; `None` before it moved.
+    pub fn duration(&self) -> Option<Duration> {
+        Some(self.last_moved?.duration_since(self.started?))
+    }
+
+    fn init(&mut self) {
+        self.initialized = true;
+        self.running = false;
+        self.stop_coordinate = get_mouse_position();
+        self.coordinate_unchanged_cycles = 0;
+    }
+
+    pub fn update(&mut self) -> RecordingStatus {
+        if !self.initialized {
+            self.init();
+        }
+        let current_mouse_coordinate: Vector2D = get_mouse_position();
+        if self.stop_coordinate != current_mouse_coordinate {
+            self.running = true;
+            self.coordinates.push(current_mouse_coordinate);
+            self.stop_coordinate = current_mouse_coordinate;
+            let now: Instant = Instant::now();
+            self.started.get_or_insert(now);
+            self.last_moved = Some(now);
+            if self.coordinates.len() == 1 {
+                RecordingStatus::Started
+            } else {
+                RecordingStatus::Running
+            }
+        } else if self.running {
+            self.coordinate_unchanged_cycles += 1;
+            if self.coordinate_unchanged_cycles >= END_FIGURE_TIMEOUT {
+                self.initialized = false;
+                self.stop_coordinate = get_mouse_position();
+                RecordingStatus::Finished
+            } else {
+                RecordingStatus::Running
+            }
+        } else {
+            RecordingStatus::Waiting
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Splitting strokes at corners into lines and arcs, and matching the result against patterns.
+//!
+//! A pattern is a sequence of segments separated by whitespace. A line is written as its
+//! direction (`R`, `DL`, `right`, `→`, ...) or as `line(<direction>)`, an arc as its winding
+//! (`CW`, `CCW`, `↻`, `↺`) or as `arc(<winding>)`. Plain `line` and `arc` match any direction.
+//!
+//! ```text
+//! D R                  an L-shape
+//! DR UR                a check mark
+//! line(→) line(↓)      right, then down
+//! R arc(cw) L          right, a clockwise arc, then back to the left
+//! ```
+//!
+//! Like in the `direction` condition of bindings, `R`, `D`, `L` and `U` accept anything closer to
+//! them than to the other main directions, while diagonals must match exactly.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::direction::{Direction, Winding};
+use crate::shape::{Shape, ShapeName, Swipe};
+use crate::TOLERANCE_GENERAL;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SegmentKind {
+    Line(Swipe),
+    Arc(Winding),
+}
+
+/// A part of a stroke between two corners. `start` and `end` are inclusive indices into `Shape.coordinates`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Segment {
+    pub start: usize,
+    pub end: usize,
+    pub kind: SegmentKind,
+}
+
+/// One element of a [`Pattern`]; `None` matches any direction or winding.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Token {
+    Line(Option<Direction>),
+    Arc(Option<Winding>),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Pattern {
+    pub tokens: Vec<Token>,
+}
+
+impl Token {
+    pub fn matches(&self, kind: &SegmentKind) -> bool {
+        match (self, kind) {
+            (Token::Line(None), SegmentKind::Line(_)) | (Token::Arc(None), SegmentKind::Arc(_)) => true,
+            (Token::Line(Some(direction)), SegmentKind::Line(swipe)) if direction.is_diagonal() => swipe.direction == *direction,
+            (Token::Line(Some(direction)), SegmentKind::Line(swipe)) => swipe.direction_4 == *direction,
+            (Token::Arc(Some(winding)), SegmentKind::Arc(other)) => winding == other,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Line(Some(direction)) => f.write_str(direction.abbreviation()),
+            Token::Line(None) => f.write_str("line"),
+            Token::Arc(Some(Winding::Clockwise)) => f.write_str("CW"),
+            Token::Arc(Some(Winding::CounterClockwise)) => f.write_str("CCW"),
+            Token::Arc(None) => f.write_str("arc"),
+        }
+    }
+}
+
+impl FromStr for Token {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let argument = |prefix: &str| text.strip_prefix(prefix).and_then(|rest| rest.strip_suffix(')'));
+        if text.eq_ignore_ascii_case("line") {
+            Ok(Token::Line(None))
+        } else if text.eq_ignore_ascii_case("arc") {
+            Ok(Token::Arc(None))
+        } else if let Some(direction) = argument("line(") {
+            Ok(Token::Line(Some(direction.parse()?)))
+        } else if let Some(winding) = argument("arc(") {
+            Ok(Token::Arc(Some(winding.parse()?)))
+        } else if let Ok(direction) = text.parse() {
+            Ok(Token::Line(Some(direction)))
+        } else if let Ok(winding) = text.parse() {
+            Ok(Token::Arc(Some(winding)))
+        } else {
+            Err(format!("expected a line direction or an arc winding, found `{}`", text))
+        }
+    }
+}
+
+impl Pattern {
+    /// The most specific pattern describing `segments`, with the exact direction of every line.
+    pub fn from_segments(segments: &[Segment]) -> Self {
+        let tokens: Vec<Token> = segments.iter().map(|segment| match segment.kind {
+            SegmentKind::Line(swipe) => Token::Line(Some(swipe.direction)),
+            SegmentKind::Arc(winding) => Token::Arc(Some(winding)),
+        }).collect();
+        Pattern { tokens }
+    }
+
+    pub fn matches(&self, segments: &[Segment]) -> bool {
+        self.tokens.len() == segments.len() && self.tokens.iter().zip(segments).all(|(token, segment)| token.matches(&segment.kind))
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tokens: Vec<String> = self.tokens.iter().map(Token::to_string).collect();
+        f.write_str(&tokens.join(" "))
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<Token> = text.split_whitespace().map(str::parse).collect::<Result<Vec<Token>, String>>()?;
+        if tokens.is_empty() {
+            return Err(String::from("expected at least one segment"));
+        }
+        Ok(Pattern { tokens })
+    }
+}
+
+impl Shape {
+    /// The stroke split at its corners, each part classified as a line or an arc.
+    pub fn segments(&self) -> Vec<Segment> {
+        if self.coordinates.is_empty() {
+            return Vec::new();
+        }
+        let mut bounds: Vec<usize> = vec![0];
+        bounds.extend(self.corners().iter().map(|corner| corner.index));
+        bounds.push(self.coordinates.len() - 1);
+        let mut segments: Vec<Segment> = Vec::new();
+        for pair in bounds.windows(2) {
+            let part: Shape = Shape { coordinates: self.coordinates[pair[0]..=pair[1]].to_vec(), shape_type: ShapeName::Undefined };
+            let Some(swipe) = part.swipe() else {
+                continue;
+            };
+            let kind: SegmentKind = match part.winding() {
+                Some(winding) if part.line_score() < 100.0 - 100.0 * TOLERANCE_GENERAL => SegmentKind::Arc(winding),
+                _ => SegmentKind::Line(swipe),
+            };
+            segments.push(Segment { start: pair[0], end: pair[1], kind });
+        }
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::fixtures::polyline;
+    use crate::vector::{Vector2D, Vector2F};
+
+    #[test]
+    fn test_segments() {
+        let l_shape: Shape = polyline(&[(100, 100), (100, 400), (400, 400)]);
+        assert_eq!(l_shape.corners().iter().map(|corner| corner.index).collect::<Vec<usize>>(), vec![60]);
+        assert_eq!(Pattern::from_segments(&l_shape.segments()).to_string(), "D R");
+        let check: Shape = polyline(&[(100, 300), (200, 400), (400, 100)]);
+        assert!("DR UR".parse::<Pattern>().unwrap().matches(&check.segments()));
+        assert!(!"line(→) line(↓)".parse::<Pattern>().unwrap().matches(&check.segments()));
+        let zig_zag: Shape = polyline(&[(100, 100), (400, 110), (120, 300), (420, 320)]);
+        assert_eq!(Pattern::from_segments(&zig_zag.segments()).to_string(), "R DL R");
+
+        let mut coordinates: Vec<Vector2D> = polyline(&[(400, -50), (400, 100)]).coordinates;
+        for i in 1..=40 {
+            let angle: f64 = std::f64::consts::PI * i as f64 / 40.0;
+            coordinates.push((Vector2F::new(400.0, 250.0) + Vector2F::new(angle.sin(), -angle.cos()) * 150.0).round());
+        }
+        let hook: Shape = Shape { coordinates, shape_type: ShapeName::Undefined };
+        assert_eq!(Pattern::from_segments(&hook.segments()).to_string(), "D CW");
+        assert!("line(↓) arc".parse::<Pattern>().unwrap().matches(&hook.segments()));
+        assert!("x y".parse::<Pattern>().unwrap_err().contains("`x`"));
+    }
+}
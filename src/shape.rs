@@ -0,0 +1,573 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::arrow::Arrow;
+use crate::direction::{Direction, Winding};
+use crate::distances::{closest_pair, farthest_pair};
+use crate::hull::signed_area;
+use crate::limits::Rejection;
+use crate::recognizer::{Classifier, Hypothesis};
+use crate::star::Star;
+use crate::vector::{Vector2D, Vector2F};
+use crate::pca::{AxisEllipse, MajorAxis, PrincipalComponents};
+use crate::{CIRCLE_TOLERANCE, ELLIPSE_TOLERANCE, LINE_TOLERANCE_PX};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ShapeName {
+    Circle,
+    Ellipse,
+    Line,
+    Arrow,
+    Star,
+    /// A check mark (✓).
+    Check,
+    /// An X drawn in one stroke, or an alpha loop.
+    Cross,
+    /// A zig-zag or scribble, as used to scratch something out.
+    ZigZag,
+    /// A caret (^).
+    Caret,
+    Heart,
+    /// A rhombus standing on one of its corners.
+    Diamond,
+    Teardrop,
+    Crescent,
+    Cloud,
+    /// A shape recognized by a [`ShapeRecognizer`](crate::recognizer::ShapeRecognizer) from outside of this crate.
+    Custom(String),
+    Unknown,
+    Undefined,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Shape {
+    pub coordinates: Vec<Vector2D>,
+    pub shape_type: ShapeName,
+}
+
+/// Result of [`Shape::classify`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Classification {
+    pub shape_name: ShapeName,
+    /// The shape the score was computed for; only differs from `shape_name` if that is `Unknown`.
+    pub candidate: ShapeName,
+    /// How well the stroke matched `candidate`, in percent.
+    pub score: f64,
+    /// Direction and length, if the shape is a line or an arrow.
+    pub swipe: Option<Swipe>,
+    /// Drawing direction, if the shape is closed (a circle, an ellipse, a star or one of the catalogue).
+    pub winding: Option<Winding>,
+    /// Tail and tip, if the shape is an arrow.
+    pub arrow: Option<Arrow>,
+    /// Number of points and centre, if the shape is a star.
+    pub star: Option<Star>,
+    /// Where the gesture was drawn, if it is a check mark, a cross, a zig-zag or a caret.
+    pub bounding_box: Option<BoundingBox>,
+    /// Orientation and elongation, if the shape is a line or an ellipse.
+    pub major_axis: Option<MajorAxis>,
+    /// Why the stroke was not classified at all; `shape_name` and `candidate` are `Unknown` then.
+    pub rejection: Option<Rejection>,
+}
+
+/// A line seen as a swipe gesture, from its first to its last point.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Swipe {
+    /// Nearest of the 8 compass directions.
+    pub direction: Direction,
+    /// Nearest of the 4 main directions.
+    pub direction_4: Direction,
+    /// Angle in radians in `(-π, π]`, counter-clockwise as seen on screen; 0 is right, π/2 is up.
+    pub angle: f64,
+    pub length: f64,
+}
+
+/// The smallest axis-aligned rectangle containing all points, in screen coordinates.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BoundingBox {
+    pub min: Vector2F,
+    pub max: Vector2F,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DistanceSet {
+    pub min: f64,
+    pub max: f64,
+    pub max_pair: [Vector2F; 2],
+    pub min_pair: [Vector2F; 2],
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PointDistanceSet {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    pub above: i32,
+    pub below: i32,
+    pub values: i32,
+    pub passes_percent: f64,
+    pub max_pair: [Vector2F; 2],
+    pub min_pair: [Vector2F; 2],
+}
+
+impl FromStr for ShapeName {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_ascii_lowercase().as_str() {
+            "circle" => Ok(ShapeName::Circle),
+            "ellipse" => Ok(ShapeName::Ellipse),
+            "line" => Ok(ShapeName::Line),
+            "arrow" => Ok(ShapeName::Arrow),
+            "star" => Ok(ShapeName::Star),
+            "check" => Ok(ShapeName::Check),
+            "cross" | "x" => Ok(ShapeName::Cross),
+            "zigzag" | "zig-zag" | "scribble" => Ok(ShapeName::ZigZag),
+            "caret" => Ok(ShapeName::Caret),
+            "heart" => Ok(ShapeName::Heart),
+            "diamond" | "rhombus" => Ok(ShapeName::Diamond),
+            "teardrop" | "drop" => Ok(ShapeName::Teardrop),
+            "crescent" | "moon" => Ok(ShapeName::Crescent),
+            "cloud" => Ok(ShapeName::Cloud),
+            "unknown" => Ok(ShapeName::Unknown),
+            _ => Err(format!("unknown shape `{}`", name)),
+        }
+    }
+}
+
+impl fmt::Display for ShapeName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShapeName::Custom(name) => f.write_str(name),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+impl Swipe {
+    /// Direction, angle and length of a vector in screen coordinates; `None` for the zero vector.
+    pub fn from_vector(vector: Vector2F) -> Option<Self> {
+        if vector == Vector2F::default() {
+            return None;
+        }
+        Some(Swipe { direction: Direction::from_vector(vector), direction_4: Direction::from_vector_4(vector), angle: (-vector.y).atan2(vector.x), length: vector.abs() })
+    }
+}
+
+impl BoundingBox {
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+}
+
+impl Classification {
+    pub fn new(shape_name: ShapeName, candidate: ShapeName, score: f64) -> Self {
+        Classification { shape_name, candidate, score, swipe: None, winding: None, arrow: None, star: None, bounding_box: None, major_axis: None, rejection: None }
+    }
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(rejection) = self.rejection {
+            write!(f, "{} ({})", self.shape_name.to_string().to_uppercase(), rejection)
+        } else if let (true, Some(swipe)) = (self.shape_name == self.candidate, self.swipe) {
+            write!(f, "{} ({}%, {}, {:.0} px)", self.shape_name.to_string().to_uppercase(), self.score as i32, swipe.direction, swipe.length)
+        } else if let (true, Some(star)) = (self.shape_name == self.candidate, self.star) {
+            write!(f, "{} ({}%, {} points)", self.shape_name.to_string().to_uppercase(), self.score as i32, star.points)
+        } else if let (true, Some(winding)) = (self.shape_name == self.candidate, self.winding) {
+            write!(f, "{} ({}%, {})", self.shape_name.to_string().to_uppercase(), self.score as i32, winding)
+        } else if self.shape_name == self.candidate {
+            write!(f, "{} ({}%)", self.shape_name.to_string().to_uppercase(), self.score as i32)
+        } else {
+            write!(f, "{} ({}% {})", self.shape_name.to_string().to_uppercase(), self.score as i32, self.candidate)
+        }
+    }
+}
+
+impl Shape {
+    /// Classifies the shape and prints the result.
+    pub fn get_shape_name(&self) -> ShapeName {
+        let classification: Classification = self.classify();
+        println!("{}", classification);
+        classification.shape_name
+    }
+
+    /// Classifies the shape with the built-in recognizers; see [`Classifier`].
+    pub fn classify(&self) -> Classification {
+        Classifier::default().classify(self)
+    }
+
+    /// Every shape the stroke might be according to the built-in recognizers, most probable first.
+    pub fn rank(&self) -> Vec<Hypothesis> {
+        Classifier::default().rank(self)
+    }
+
+    /// How well the stroke matches the ellipse along its principal axes, in percent: the share of
+    /// points whose distance from its centre is within `ELLIPSE_TOLERANCE` of the ellipse's in the
+    /// same direction. `None` if the points lie on no such ellipse.
+    pub(crate) fn ellipse_score(&self) -> Option<f64> {
+        let components: PrincipalComponents = self.principal_components();
+        let points: Vec<Vector2F> = self.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+        let ellipse: AxisEllipse = components.fit_ellipse(&points)?;
+        let centre: Vector2F = components.project(ellipse.centre);
+        let passed: usize = points.iter().filter(|point| {
+            let relative: Vector2F = components.project(**point) - centre;
+            (Vector2F::new(relative.x / ellipse.radii.x, relative.y / ellipse.radii.y).abs() - 1.0).abs() <= ELLIPSE_TOLERANCE
+        }).count();
+        Some(passed as f64 / points.len() as f64 * 100.0)
+    }
+
+    /// Percentage of points within `LINE_TOLERANCE_PX` of the line through the first and the last point.
+    pub fn line_score(&self) -> f64 {
+        let (Some(start), Some(end)) = (self.coordinates.first(), self.coordinates.last()) else {
+            return 0.0;
+        };
+        let passed: usize = self.coordinates.iter().filter(|coordinate| coordinate.to_f64().distance_to_line(start.to_f64(), end.to_f64()).abs() <= LINE_TOLERANCE_PX).count();
+        passed as f64 / self.coordinates.len() as f64 * 100.0
+    }
+
+    /// Direction, angle and length from the first to the last point; `None` if they are the same.
+    pub fn swipe(&self) -> Option<Swipe> {
+        Swipe::from_vector(self.coordinates.last()?.to_f64() - self.coordinates.first()?.to_f64())
+    }
+
+    /// Drawing direction from the signed area of the stroke (shoelace formula), closing it
+    /// from the last point back to the first; `None` if the stroke encloses no area.
+    pub fn winding(&self) -> Option<Winding> {
+        let points: Vec<Vector2F> = self.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+        // Screen coordinates point down, so a positive area is clockwise on screen.
+        let area: f64 = signed_area(&points);
+        if area > 0.0 {
+            Some(Winding::Clockwise)
+        } else if area < 0.0 {
+            Some(Winding::CounterClockwise)
+        } else {
+            None
+        }
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        let mut bounding_box: BoundingBox = BoundingBox { min: Vector2F::new(f64::MAX, f64::MAX), max: Vector2F::new(f64::MIN, f64::MIN) };
+        for coordinate in &self.coordinates {
+            let point: Vector2F = coordinate.to_f64();
+            bounding_box.min = Vector2F::new(bounding_box.min.x.min(point.x), bounding_box.min.y.min(point.y));
+            bounding_box.max = Vector2F::new(bounding_box.max.x.max(point.x), bounding_box.max.y.max(point.y));
+        }
+        bounding_box
+    }
+
+    /// Average of all points; the origin for a stroke without any.
+    pub fn find_centre(&self) -> Vector2F {
+        if self.coordinates.is_empty() {
+            return Vector2F::default();
+        }
+        let mut average_coordinate: Vector2F = Vector2F::default();
+        for coordinate in &self.coordinates {
+            average_coordinate += coordinate.to_f64();
+        }
+        average_coordinate / self.coordinates.len() as f64
+    }
+
+    pub fn get_closest_to_point(&self, point: Vector2F) -> (Vector2D, f64) {
+        let mut min_distance: f64 = f64::MAX;
+        let mut min_distance_point: Vector2D = Vector2D::default();
+        for other in &self.coordinates {
+            let distance: f64 = point.distance(other.to_f64());
+            if distance != 0.0 && distance < min_distance {
+                min_distance = distance;
+                min_distance_point = *other;
+            }
+        }
+        (min_distance_point, min_distance)
+    }
+
+    /// Largest and smallest distance between two points at different positions.
+    /// The farthest pair is searched on the convex hull and the closest with a sweep line,
+    /// so this stays fast for long recordings.
+    pub fn get_distances(&self) -> DistanceSet {
+        let mut distances: DistanceSet = DistanceSet { min: f64::MAX, max: 0.0, max_pair: [Vector2F::default(); 2], min_pair: [Vector2F::default(); 2] };
+        if let Some((a, b)) = farthest_pair(&self.coordinates, &self.convex_hull()) {
+            distances.max_pair = [self.coordinates[a].to_f64(), self.coordinates[b].to_f64()];
+            distances.max = distances.max_pair[0].distance(distances.max_pair[1]);
+        }
+        if let Some((a, b)) = closest_pair(&self.coordinates) {
+            distances.min_pair = [self.coordinates[a].to_f64(), self.coordinates[b].to_f64()];
+            distances.min = distances.min_pair[0].distance(distances.min_pair[1]);
+        }
+        distances
+    }
+
+    pub fn get_point_distances(&self, point: Vector2F) -> PointDistanceSet {
+        let mut max_distance: f64 = 0.0;
+        let mut min_distance: f64 = f64::MAX;
+        let mut average: f64 = 0.0;
+        let mut distances: Vec<f64> = Vec::new();
+        let mut max_pair: [Vector2F; 2] = [Vector2F::default(), Vector2F::default()];
+        let mut min_pair: [Vector2F; 2] = max_pair;
+        for other in &self.coordinates {
+            let other: Vector2F = other.to_f64();
+            if point != other {
+                let new_distance: f64 = point.distance(other);
+                average += new_distance;
+                distances.push(new_distance);
+                if new_distance > max_distance {
+                    max_distance = new_distance;
+                    max_pair = [point, other];
+                }
+                if new_distance < min_distance {
+                    min_distance = new_distance;
+                    min_pair = [point, other];
+                }
+            }
+        }
+        average /= self.coordinates.len().max(1) as f64;
+        let absolute_tolerance: f64 = average * CIRCLE_TOLERANCE;
+        let mut above: i32 = 0;
+        let mut below: i32 = 0;
+        for distance in &distances {
+            if distance - absolute_tolerance > average {
+                above += 1;
+            }
+            if distance + absolute_tolerance < average {
+                below += 1;
+            }
+        }
+        let passed: i32 = self.coordinates.len() as i32 - (above + below);
+        PointDistanceSet { min: min_distance, max: max_distance, avg: average, above, below, values: self.coordinates.len() as i32, passes_percent: (passed as f64) / (self.coordinates.len() as f64) * 100.0, max_pair, min_pair }
+    }
+}
+
+/// Strokes drawn along straight lines, for the tests of the modules that recognize corners.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use std::f64::consts::{PI, TAU};
+
+    use super::*;
+    use crate::synthetic::{Figure, Generator};
+
+    /// Straight lines through `corners` with a point about every 5 px.
+    pub(crate) fn polyline_through(corners: &[Vector2F]) -> Shape {
+        let mut coordinates: Vec<Vector2D> = Vec::new();
+        for pair in corners.windows(2) {
+            let steps: usize = (pair[0].distance(pair[1]) / 5.0) as usize;
+            coordinates.extend((0..steps).map(|i| pair[0].lerp(pair[1], i as f64 / steps as f64).round()));
+        }
+        coordinates.extend(corners.last().map(|corner| corner.round()));
+        Shape { coordinates, shape_type: ShapeName::Undefined }
+    }
+
+    /// Like [`polyline_through`], for corners on whole pixels.
+    pub(crate) fn polyline(corners: &[(i32, i32)]) -> Shape {
+        polyline_through(&corners.iter().map(|&(x, y)| Vector2D { x, y }.to_f64()).collect::<Vec<Vector2F>>())
+    }
+
+    pub(crate) fn heart_curve(t: f64) -> Vector2F {
+        let t: f64 = t * TAU;
+        Vector2F::new(16.0 * t.sin().powi(3), -(13.0 * t.cos() - 5.0 * (2.0 * t).cos() - 2.0 * (3.0 * t).cos() - (4.0 * t).cos())) / 16.0
+    }
+
+    pub(crate) fn teardrop_curve(t: f64) -> Vector2F {
+        let t: f64 = t * TAU;
+        Vector2F::new(t.cos(), t.sin() * (t / 2.0).sin())
+    }
+
+    /// The closed polygon through `corners`, the same time on every side.
+    pub(crate) fn polygon(corners: &[(f64, f64)]) -> impl Fn(f64) -> Vector2F + '_ {
+        move |t: f64| {
+            let position: f64 = t.rem_euclid(1.0) * corners.len() as f64;
+            let i: usize = position as usize % corners.len();
+            let (a, b) = (corners[i], corners[(i + 1) % corners.len()]);
+            Vector2F::new(a.0, a.1).lerp(Vector2F::new(b.0, b.1), position.fract())
+        }
+    }
+
+    /// Like [`polygon`], without closing it; parameters outside of 0 to 1 stay at its ends.
+    pub(crate) fn path(corners: &[(f64, f64)]) -> impl Fn(f64) -> Vector2F + '_ {
+        move |t: f64| {
+            let position: f64 = t.clamp(0.0, 1.0) * (corners.len() - 1) as f64;
+            let i: usize = (position as usize).min(corners.len() - 2);
+            let (a, b) = (corners[i], corners[i + 1]);
+            Vector2F::new(a.0, a.1).lerp(Vector2F::new(b.0, b.1), position - i as f64)
+        }
+    }
+
+    /// An outer arc of the unit circle and an inner arc of a smaller circle shifted to the right.
+    pub(crate) fn crescent_curve(t: f64) -> Vector2F {
+        let (radius, shift) = (0.85, 0.4);
+        let x: f64 = (1.0 - radius * radius + shift * shift) / (2.0 * shift);
+        let horn: f64 = (1.0 - x * x).sqrt().atan2(x);
+        let inner_horn: f64 = (1.0 - x * x).sqrt().atan2(x - shift);
+        if t < 0.5 {
+            let angle: f64 = horn + (TAU - 2.0 * horn) * t * 2.0;
+            Vector2F::new(angle.cos(), angle.sin())
+        } else {
+            let angle: f64 = TAU - inner_horn - (TAU - 2.0 * inner_horn) * (t - 0.5) * 2.0;
+            Vector2F::new(shift + radius * angle.cos(), radius * angle.sin())
+        }
+    }
+
+    /// Half circles on the sides of a regular polygon with `bumps` corners.
+    pub(crate) fn cloud_curve(bumps: usize) -> impl Fn(f64) -> Vector2F {
+        move |t: f64| {
+            let position: f64 = t * bumps as f64;
+            let i: f64 = position.floor();
+            let (a, b) = (Vector2F::new(1.0, 0.0).rotate(TAU * i / bumps as f64), Vector2F::new(1.0, 0.0).rotate(TAU * (i + 1.0) / bumps as f64));
+            let middle: Vector2F = a.lerp(b, 0.5);
+            middle + (a - middle).rotate(PI * position.fract())
+        }
+    }
+
+    /// Generated strokes of every built-in shape, and some that are none of them, labelled with what
+    /// they should be recognized as. The same every time.
+    pub(crate) fn corpus() -> Vec<(Shape, ShapeName)> {
+        let pentagram: Vec<(f64, f64)> = [0, 4, 8, 2, 6].iter().map(|&i| Vector2F::new(0.0, -0.5).rotate(TAU * i as f64 / 10.0)).map(|tip| (tip.x, tip.y)).collect();
+        let outline: Vec<(f64, f64)> = (0..10).map(|i| Vector2F::new(0.0, if i % 2 == 0 { -0.5 } else { -0.2 }).rotate(TAU * i as f64 / 10.0)).map(|tip| (tip.x, tip.y)).collect();
+        let open: [(&[(f64, f64)], ShapeName); 9] = [
+            (&[(-0.5, 0.0), (0.5, 0.0), (0.35, -0.1), (0.5, 0.0), (0.35, 0.1)], ShapeName::Arrow),
+            (&[(-0.5, 0.0), (0.5, 0.0), (0.3, -0.15), (0.3, 0.15), (0.5, 0.0)], ShapeName::Arrow),
+            (&[(-0.5, 0.0), (0.5, 0.0), (0.35, -0.1)], ShapeName::Unknown),
+            (&[(-0.3, 0.2), (-0.15, 0.4), (0.3, -0.4)], ShapeName::Check),
+            (&[(-0.4, 0.4), (0.0, -0.4), (0.4, 0.4)], ShapeName::Caret),
+            (&[(-0.4, -0.3), (0.4, -0.25), (-0.35, -0.1), (0.45, -0.05), (-0.3, 0.1), (0.5, 0.15)], ShapeName::ZigZag),
+            (&[(-0.4, -0.4), (0.4, 0.4), (0.4, -0.4), (-0.4, 0.4)], ShapeName::Cross),
+            (&[(-0.4, -0.2), (0.0, 0.2), (0.4, -0.2)], ShapeName::Unknown),
+            (&[(-0.4, 0.4), (-0.4, -0.4), (0.4, 0.4), (0.4, -0.4)], ShapeName::Unknown),
+        ];
+        let closed: [(&[(f64, f64)], ShapeName); 3] = [(&pentagram, ShapeName::Star), (&outline, ShapeName::Star), (&[(0.0, -0.5), (0.3, 0.0), (0.0, 0.5), (-0.3, 0.0)], ShapeName::Diamond)];
+        let mut corpus: Vec<(Shape, ShapeName)> = Vec::new();
+        let mut generator: Generator = Generator { samples: 120, seed: 7, ..Generator::default() };
+        for round in 0..12 {
+            generator.noise = (round / 4) as f64;
+            // Carets, check marks and diamonds depend on which way is up, so they are barely rotated.
+            generator.rotation = (round % 4) as f64 * 0.05 - 0.075;
+            let figures: [(Figure, ShapeName); 8] = [
+                (Figure::Circle, ShapeName::Circle),
+                (Figure::Ellipse { aspect: 0.4 + 0.05 * (round % 4) as f64 }, ShapeName::Ellipse),
+                (Figure::Line, ShapeName::Line),
+                (Figure::Polygon { sides: 3 }, ShapeName::Unknown),
+                (Figure::Polygon { sides: 4 }, ShapeName::Unknown),
+                (Figure::Arc { sweep: PI }, ShapeName::Unknown),
+                (Figure::Arc { sweep: 1.5 * PI }, ShapeName::Unknown),
+                (Figure::Spiral { turns: 2.0 }, ShapeName::Unknown),
+            ];
+            for (figure, shape_name) in figures {
+                corpus.push((generator.stroke(figure).shape(), shape_name));
+            }
+            for (corners, shape_name) in &open {
+                corpus.push((generator.trace(path(corners)).shape(), shape_name.clone()));
+            }
+            for (corners, shape_name) in &closed {
+                corpus.push((generator.trace(polygon(corners)).shape(), shape_name.clone()));
+            }
+            let curves: [(&dyn Fn(f64) -> Vector2F, ShapeName); 4] = [(&heart_curve, ShapeName::Heart), (&teardrop_curve, ShapeName::Teardrop), (&crescent_curve, ShapeName::Crescent), (&cloud_curve(6), ShapeName::Cloud)];
+            for (curve, shape_name) in curves {
+                corpus.push((generator.trace(|t| curve(t.rem_euclid(1.0)) * 0.5).shape(), shape_name));
+            }
+        }
+        corpus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circle() {
+        let example_circle_coordinates_vec: Vec<Vec<Vector2D>> = vec![vec![Vector2D { x: 2623, y: 359 }, Vector2D { x: 2637, y: 340 }, Vector2D { x: 2665, y: 313 }, Vector2D { x: 2688, y: 292 }, Vector2D { x: 2722, y: 267 }, Vector2D { x: 2762, y: 243 }, Vector2D { x: 2800, y: 228 }, Vector2D { x: 2849, y: 222 }, Vector2D { x: 2896, y: 227 }, Vector2D { x: 2941, y: 239 }, Vector2D { x: 2983, y: 262 }, Vector2D { x: 3015, y: 296 }, Vector2D { x: 3038, y: 335 }, Vector2D { x: 3050, y: 381 }, Vector2D { x: 3045, y: 421 }, Vector2D { x: 3031, y: 454 }, Vector2D { x: 3008, y: 498 }, Vector2D { x: 2983, y: 528 }, Vector2D { x: 2942, y: 555 }, Vector2D { x: 2904, y: 574 }, Vector2D { x: 2864, y: 586 }, Vector2D { x: 2818, y: 591 }, Vector2D { x: 2782, y: 590 }, Vector2D { x: 2752, y: 581 }, Vector2D { x: 2722, y: 568 }, Vector2D { x: 2692, y: 547 }, Vector2D { x: 2671, y: 518 }, Vector2D { x: 2658, y: 476 }, Vector2D { x: 2665, y: 427 }, Vector2D { x: 2677, y: 376 }, Vector2D { x: 2684, y: 344 }]];
+        for circle_coordinates in example_circle_coordinates_vec {
+            let test_shape: Shape = Shape { coordinates: circle_coordinates, shape_type: ShapeName::Undefined };
+            assert_eq!(test_shape.get_shape_name(), ShapeName::Circle);
+        }
+    }
+
+    #[test]
+    fn test_ellipse() {
+        let example_ellipse_coordinates_vec: Vec<Vec<Vector2D>> = vec![vec![Vector2D { x: 2909, y: 424 }, Vector2D { x: 2886, y: 424 }, Vector2D { x: 2856, y: 423 }, Vector2D { x: 2822, y: 422 }, Vector2D { x: 2779, y: 427 }, Vector2D { x: 2697, y: 439 }, Vector2D { x: 2595, y: 461 }, Vector2D { x: 2557, y: 474 }, Vector2D { x: 2535, y: 488 }, Vector2D { x: 2522, y: 507 }, Vector2D { x: 2516, y: 529 }, Vector2D { x: 2524, y: 551 }, Vector2D { x: 2551, y: 574 }, Vector2D { x: 2638, y: 594 }, Vector2D { x: 2787, y: 602 }, Vector2D { x: 2946, y: 600 }, Vector2D { x: 3085, y: 600 }, Vector2D { x: 3253, y: 596 }, Vector2D { x: 3343, y: 580 }, Vector2D { x: 3396, y: 552 }, Vector2D { x: 3404, y: 533 }, Vector2D { x: 3405, y: 520 }, Vector2D { x: 3394, y: 504 }, Vector2D { x: 3362, y: 484 }, Vector2D { x: 3324, y: 469 }, Vector2D { x: 3281, y: 456 }, Vector2D { x: 3243, y: 446 }, Vector2D { x: 3183, y: 433 }, Vector2D { x: 3122, y: 425 }, Vector2D { x: 3033, y: 421 }, Vector2D { x: 2979, y: 421 }, Vector2D { x: 2936, y: 424 }, Vector2D { x: 2926, y: 425 }, Vector2D { x: 2923, y: 425 }, Vector2D { x: 2915, y: 425 }, Vector2D { x: 2909, y: 425 }, Vector2D { x: 2906, y: 425 }, Vector2D { x: 2903, y: 425 }, Vector2D { x: 2902, y: 425 }]];
+        for ellipse_coordinates in example_ellipse_coordinates_vec {
+            let test_shape: Shape = Shape { coordinates: ellipse_coordinates, shape_type: ShapeName::Undefined };
+            assert_eq!(test_shape.get_shape_name(), ShapeName::Ellipse);
+        }
+    }
+
+    #[test]
+    fn test_line() {
+        let example_line_coordinates_vec: Vec<Vec<Vector2D>> = vec![vec![Vector2D { x: 3659, y: 919 }, Vector2D { x: 3655, y: 919 }, Vector2D { x: 3654, y: 919 }, Vector2D { x: 3651, y: 919 }, Vector2D { x: 3645, y: 919 }, Vector2D { x: 3625, y: 919 }, Vector2D { x: 3609, y: 920 }, Vector2D { x: 3587, y: 920 }, Vector2D { x: 3565, y: 921 }, Vector2D { x: 3538, y: 923 }, Vector2D { x: 3520, y: 924 }, Vector2D { x: 3496, y: 924 }, Vector2D { x: 3475, y: 924 }, Vector2D { x: 3448, y: 924 }, Vector2D { x: 3418, y: 922 }, Vector2D { x: 3391, y: 921 }, Vector2D { x: 3361, y: 921 }, Vector2D { x: 3333, y: 921 }, Vector2D { x: 3305, y: 921 }, Vector2D { x: 3283, y: 920 }, Vector2D { x: 3258, y: 919 }, Vector2D { x: 3237, y: 919 }, Vector2D { x: 3211, y: 918 }, Vector2D { x: 3190, y: 917 }, Vector2D { x: 3164, y: 916 }, Vector2D { x: 3138, y: 916 }, Vector2D { x: 3111, y: 918 }, Vector2D { x: 3084, y: 919 }, Vector2D { x: 3052, y: 922 }, Vector2D { x: 3029, y: 923 }, Vector2D { x: 2999, y: 924 }, Vector2D { x: 2970, y: 925 }, Vector2D { x: 2935, y: 926 }, Vector2D { x: 2911, y: 927 }, Vector2D { x: 2892, y: 927 }, Vector2D { x: 2883, y: 928 }, Vector2D { x: 2878, y: 928 }]];
+        for line_coordinates in example_line_coordinates_vec {
+            let test_shape: Shape = Shape { coordinates: line_coordinates, shape_type: ShapeName::Undefined };
+            assert_eq!(test_shape.get_shape_name(), ShapeName::Line);
+        }
+        // Three points 10 px apart are too few and too small to tell.
+        let short: Shape = Shape { coordinates: vec![Vector2D { x: 5, y: 5}, Vector2D { x: 10, y: 5}, Vector2D { x: 15, y: 5}], shape_type: ShapeName::Undefined };
+        assert_eq!(short.classify().rejection, Some(Rejection::TooFewPoints));
+    }
+
+    #[test]
+    fn test_junk() {
+        let example_junk_coordinates_vec: Vec<Vec<Vector2D>> = vec![vec![Vector2D { x: 3166, y: 539 }, Vector2D { x: 3121, y: 497 }, Vector2D { x: 3030, y: 432 }, Vector2D { x: 2939, y: 397 }, Vector2D { x: 2854, y: 400 }, Vector2D { x: 2767, y: 449 }, Vector2D { x: 2712, y: 503 }, Vector2D { x: 2673, y: 554 }, Vector2D { x: 2639, y: 590 }, Vector2D { x: 2592, y: 631 }, Vector2D { x: 2543, y: 655 }, Vector2D { x: 2495, y: 669 }, Vector2D { x: 2435, y: 678 }, Vector2D { x: 2378, y: 683 }, Vector2D { x: 2358, y: 678 }, Vector2D { x: 2338, y: 648 }, Vector2D { x: 2332, y: 638 }, Vector2D { x: 2332, y: 637 }], vec![Vector2D { x: 2878, y: 667 }, Vector2D { x: 2867, y: 654 }, Vector2D { x: 2810, y: 601 }, Vector2D { x: 2780, y: 574 }, Vector2D { x: 2730, y: 520 }, Vector2D { x: 2699, y: 485 }, Vector2D { x: 2679, y: 459 }, Vector2D { x: 2676, y: 453 }, Vector2D { x: 2675, y: 453 }, Vector2D { x: 2675, y: 454 }, Vector2D { x: 2674, y: 481 }, Vector2D { x: 2667, y: 523 }, Vector2D { x: 2660, y: 563 }, Vector2D { x: 2649, y: 628 }, Vector2D { x: 2641, y: 665 }, Vector2D { x: 2637, y: 687 }, Vector2D { x: 2634, y: 701 }, Vector2D { x: 2632, y: 708 }, Vector2D { x: 2632, y: 709 }, Vector2D { x: 2626, y: 702 }, Vector2D { x: 2602, y: 664 }, Vector2D { x: 2572, y: 629 }, Vector2D { x: 2534, y: 591 }, Vector2D { x: 2493, y: 557 }, Vector2D { x: 2410, y: 508 }, Vector2D { x: 2360, y: 466 }, Vector2D { x: 2358, y: 464 }, Vector2D { x: 2359, y: 487 }, Vector2D { x: 2354, y: 560 }, Vector2D { x: 2332, y: 655 }, Vector2D { x: 2315, y: 723 }, Vector2D { x: 2304, y: 755 }, Vector2D { x: 2303, y: 758 }], vec![Vector2D { x: 2513, y: 753 }, Vector2D { x: 2515, y: 699 }, Vector2D { x: 2523, y: 589 }, Vector2D { x: 2527, y: 527 }, Vector2D { x: 2533, y: 472 }, Vector2D { x: 2534, y: 447 }, Vector2D { x: 2537, y: 431 }, Vector2D { x: 2538, y: 430 }, Vector2D { x: 2547, y: 436 }, Vector2D { x: 2580, y: 462 }, Vector2D { x: 2669, y: 532 }, Vector2D { x: 2745, y: 580 }, Vector2D { x: 2808, y: 622 }, Vector2D { x: 2837, y: 642 }, Vector2D { x: 2869, y: 663 }, Vector2D { x: 2878, y: 669 }, Vector2D { x: 2881, y: 671 }], vec![Vector2D { x: 3114, y: 661 }, Vector2D { x: 3082, y: 650 }, Vector2D { x: 3045, y: 638 }, Vector2D { x: 2985, y: 617 }, Vector2D { x: 2923, y: 595 }, Vector2D { x: 2855, y: 574 }, Vector2D { x: 2782, y: 551 }, Vector2D { x: 2743, y: 539 }, Vector2D { x: 2691, y: 521 }, Vector2D { x: 2649, y: 508 }, Vector2D { x: 2631, y: 504 }, Vector2D { x: 2632, y: 502 }, Vector2D { x: 2646, y: 491 }, Vector2D { x: 2678, y: 473 }, Vector2D { x: 2713, y: 457 }, Vector2D { x: 2757, y: 437 }, Vector2D { x: 2781, y: 425 }, Vector2D { x: 2803, y: 412 }, Vector2D { x: 2830, y: 399 }, Vector2D { x: 2878, y: 374 }, Vector2D { x: 2922, y: 350 }, Vector2D { x: 2974, y: 318 }, Vector2D { x: 3023, y: 288 }, Vector2D { x: 3051, y: 267 }, Vector2D { x: 3064, y: 258 }, Vector2D { x: 3064, y: 257 }, Vector2D { x: 3065, y: 272 }, Vector2D { x: 3076, y: 318 }, Vector2D { x: 3082, y: 351 }, Vector2D { x: 3089, y: 401 }, Vector2D { x: 3091, y: 426 }, Vector2D { x: 3094, y: 458 }, Vector2D { x: 3094, y: 492 }, Vector2D { x: 3095, y: 539 }, Vector2D { x: 3095, y: 577 }, Vector2D { x: 3095, y: 604 }, Vector2D { x: 3095, y: 618 }, Vector2D { x: 3097, y: 631 }, Vector2D { x: 3098, y: 640 }, Vector2D { x: 3099, y: 646 }], vec![Vector2D { x: 3285, y: 753 }, Vector2D { x: 3301, y: 748 }, Vector2D { x: 3308, y: 744 }, Vector2D { x: 3253, y: 742 }, Vector2D { x: 2817, y: 768 }, Vector2D { x: 2535, y: 785 }, Vector2D { x: 2748, y: 681 }, Vector2D { x: 3094, y: 563 }, Vector2D { x: 3280, y: 478 }, Vector2D { x: 3009, y: 481 }, Vector2D { x: 2599, y: 521 }, Vector2D { x: 2611, y: 498 }, Vector2D { x: 2817, y: 360 }, Vector2D { x: 3081, y: 208 }, Vector2D { x: 3099, y: 190 }, Vector2D { x: 2864, y: 240 }, Vector2D { x: 2566, y: 294 }, Vector2D { x: 2450, y: 311 }, Vector2D { x: 2449, y: 311 }], vec![Vector2D { x: 2646, y: 757 }, Vector2D { x: 2603, y: 753 }, Vector2D { x: 2568, y: 745 }, Vector2D { x: 2519, y: 737 }, Vector2D { x: 2486, y: 731 }, Vector2D { x: 2419, y: 718 }, Vector2D { x: 2376, y: 708 }, Vector2D { x: 2330, y: 690 }, Vector2D { x: 2297, y: 675 }, Vector2D { x: 2274, y: 660 }, Vector2D { x: 2258, y: 645 }, Vector2D { x: 2246, y: 627 }, Vector2D { x: 2237, y: 596 }, Vector2D { x: 2237, y: 565 }, Vector2D { x: 2251, y: 541 }, Vector2D { x: 2266, y: 529 }, Vector2D { x: 2288, y: 525 }, Vector2D { x: 2309, y: 524 }, Vector2D { x: 2348, y: 538 }, Vector2D { x: 2391, y: 553 }, Vector2D { x: 2429, y: 568 }, Vector2D { x: 2455, y: 577 }, Vector2D { x: 2487, y: 591 }, Vector2D { x: 2514, y: 599 }, Vector2D { x: 2547, y: 608 }, Vector2D { x: 2567, y: 614 }, Vector2D { x: 2586, y: 617 }, Vector2D { x: 2597, y: 617 }, Vector2D { x: 2608, y: 615 }, Vector2D { x: 2627, y: 603 }, Vector2D { x: 2658, y: 586 }, Vector2D { x: 2665, y: 577 }, Vector2D { x: 2668, y: 570 }, Vector2D { x: 2672, y: 555 }, Vector2D { x: 2671, y: 529 }, Vector2D { x: 2667, y: 478 }, Vector2D { x: 2668, y: 448 }, Vector2D { x: 2679, y: 425 }, Vector2D { x: 2694, y: 412 }, Vector2D { x: 2738, y: 398 }, Vector2D { x: 2776, y: 395 }, Vector2D { x: 2791, y: 406 }, Vector2D { x: 2793, y: 430 }, Vector2D { x: 2775, y: 480 }, Vector2D { x: 2740, y: 556 }, Vector2D { x: 2724, y: 597 }, Vector2D { x: 2712, y: 624 }, Vector2D { x: 2706, y: 640 }, Vector2D { x: 2703, y: 655 }, Vector2D { x: 2714, y: 676 }, Vector2D { x: 2731, y: 688 }, Vector2D { x: 2753, y: 696 }, Vector2D { x: 2789, y: 709 }, Vector2D { x: 2831, y: 717 }, Vector2D { x: 2888, y: 727 }, Vector2D { x: 2940, y: 734 }, Vector2D { x: 3019, y: 747 }, Vector2D { x: 3083, y: 765 }, Vector2D { x: 3169, y: 799 }, Vector2D { x: 3201, y: 825 }, Vector2D { x: 3213, y: 846 }, Vector2D { x: 3208, y: 861 }, Vector2D { x: 3193, y: 874 }, Vector2D { x: 3120, y: 886 }, Vector2D { x: 2991, y: 878 }, Vector2D { x: 2862, y: 854 }, Vector2D { x: 2807, y: 835 }, Vector2D { x: 2738, y: 809 }, Vector2D { x: 2682, y: 790 }, Vector2D { x: 2601, y: 760 }, Vector2D { x: 2567, y: 750 }], vec![Vector2D { x: 2980, y: 421 }, Vector2D { x: 2931, y: 415 }, Vector2D { x: 2871, y: 426 }, Vector2D { x: 2815, y: 455 }, Vector2D { x: 2767, y: 505 }, Vector2D { x: 2738, y: 569 }, Vector2D { x: 2733, y: 633 }, Vector2D { x: 2748, y: 687 }, Vector2D { x: 2794, y: 740 }, Vector2D { x: 2859, y: 793 }, Vector2D { x: 2943, y: 821 }, Vector2D { x: 3051, y: 814 }, Vector2D { x: 3138, y: 782 }, Vector2D { x: 3192, y: 753 }, Vector2D { x: 3234, y: 715 }, Vector2D { x: 3259, y: 658 }, Vector2D { x: 3262, y: 605 }, Vector2D { x: 3233, y: 556 }, Vector2D { x: 3194, y: 513 }, Vector2D { x: 3129, y: 477 }, Vector2D { x: 3048, y: 454 }, Vector2D { x: 2962, y: 442 }, Vector2D { x: 2882, y: 435 }, Vector2D { x: 2813, y: 428 }, Vector2D { x: 2757, y: 423 }, Vector2D { x: 2701, y: 420 }, Vector2D { x: 2662, y: 420 }, Vector2D { x: 2634, y: 424 }, Vector2D { x: 2626, y: 425 }, Vector2D { x: 2625, y: 425 }, Vector2D { x: 2625, y: 426 }]];
+        for junk_coordinates in example_junk_coordinates_vec {
+            let test_shape: Shape = Shape { coordinates: junk_coordinates, shape_type: ShapeName::Undefined };
+            assert_eq!(test_shape.get_shape_name(), ShapeName::Unknown);
+        }
+    }
+
+    #[test]
+    fn test_line_directions() {
+        let directions: [(i32, i32, Direction, Direction); 8] = [(1, 0, Direction::Right, Direction::Right), (1, 1, Direction::DownRight, Direction::Down), (0, 1, Direction::Down, Direction::Down), (-1, 1, Direction::DownLeft, Direction::Down), (-1, 0, Direction::Left, Direction::Left), (-1, -1, Direction::UpLeft, Direction::Up), (0, -1, Direction::Up, Direction::Up), (1, -1, Direction::UpRight, Direction::Up)];
+        for (dx, dy, direction, direction_4) in directions {
+            // Slightly off the exact diagonal so that the 4-way direction is not a tie.
+            let bend: i32 = if dx != 0 && dy != 0 { dy } else { 0 };
+            let coordinates: Vec<Vector2D> = (0..40).map(|i| Vector2D { x: 1000 + dx * i * 10, y: 1000 + dy * i * 10 + bend * i / 5 + (i % 2) * 3 }).collect();
+            let classification: Classification = Shape { coordinates, shape_type: ShapeName::Undefined }.classify();
+            assert_eq!(classification.shape_name, ShapeName::Line);
+            let swipe: Swipe = classification.swipe.unwrap();
+            assert_eq!((swipe.direction, swipe.direction_4), (direction, direction_4));
+            let expected: Vector2F = Vector2F::new(dx as f64, -dy as f64).normalize();
+            assert!(Vector2F::new(swipe.angle.cos(), swipe.angle.sin()).distance(expected) < 0.1);
+            assert!((swipe.length - 390.0 * (dx as f64).hypot(dy as f64)).abs() < 25.0);
+        }
+        // A vertical line has no slope; the distance to it must still be measured correctly.
+        let vertical: Vec<Vector2D> = (0..30).map(|i| Vector2D { x: 500 + (i % 3) * 4, y: 100 + i * 15 }).collect();
+        assert_eq!(vertical[10].to_f64().distance_to_line(Vector2F::new(500.0, 0.0), Vector2F::new(500.0, 900.0)).abs(), 4.0);
+        assert_eq!(Shape { coordinates: vertical, shape_type: ShapeName::Undefined }.classify().swipe.map(|swipe| swipe.direction), Some(Direction::Down));
+        // Points far off the line fail the check on both sides, whichever way the line is drawn.
+        let mut bent: Vec<Vector2D> = (0..30).map(|i| Vector2D { x: 100 + i * 20, y: 300 + if (10..20).contains(&i) { 40 } else { 0 } }).collect();
+        assert_eq!(Shape { coordinates: bent.clone(), shape_type: ShapeName::Undefined }.classify().shape_name, ShapeName::Unknown);
+        bent.reverse();
+        assert_eq!(Shape { coordinates: bent, shape_type: ShapeName::Undefined }.classify().shape_name, ShapeName::Unknown);
+        assert_eq!(Shape { coordinates: vec![Vector2D { x: 5, y: 5 }; 3], shape_type: ShapeName::Undefined }.swipe(), None);
+    }
+
+    #[test]
+    fn test_winding() {
+        let mut coordinates: Vec<Vector2D> = (0..40).map(|i| {
+            let angle: f64 = std::f64::consts::TAU * i as f64 / 40.0;
+            Vector2F::new(800.0 + 200.0 * angle.cos(), 600.0 + 200.0 * angle.sin()).round()
+        }).collect();
+        // Increasing angles with y pointing down go right, then down, then left: clockwise on screen.
+        let classification: Classification = Shape { coordinates: coordinates.clone(), shape_type: ShapeName::Undefined }.classify();
+        assert_eq!((classification.shape_name, classification.winding), (ShapeName::Circle, Some(Winding::Clockwise)));
+        coordinates.reverse();
+        assert_eq!(Shape { coordinates, shape_type: ShapeName::Undefined }.classify().winding, Some(Winding::CounterClockwise));
+        assert_eq!(Shape { coordinates: vec![Vector2D { x: 1, y: 1 }, Vector2D { x: 5, y: 5 }], shape_type: ShapeName::Undefined }.winding(), None);
+    }
+}
@@ -0,0 +1,162 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::shape::Shape;
+use crate::vector::{Vector2D, Vector2F};
+
+/// A static 2D tree over the points of a stroke for nearest-neighbour and radius queries.
+/// Queries return indices into the slice the tree was built from.
+#[derive(Clone, Debug)]
+pub struct KdTree {
+    points: Vec<Vector2F>,
+    /// Point indices; every subrange is split at its middle element, alternating x and y.
+    nodes: Vec<usize>,
+}
+
+fn axis(point: Vector2F, depth: usize) -> f64 {
+    if depth.is_multiple_of(2) { point.x } else { point.y }
+}
+
+impl KdTree {
+    pub fn new(points: &[Vector2D]) -> Self {
+        let points: Vec<Vector2F> = points.iter().map(|point| point.to_f64()).collect();
+        let mut nodes: Vec<usize> = (0..points.len()).collect();
+        Self::build(&points, &mut nodes, 0);
+        KdTree { points, nodes }
+    }
+
+    fn build(points: &[Vector2F], nodes: &mut [usize], depth: usize) {
+        if nodes.len() <= 1 {
+            return;
+        }
+        let middle: usize = nodes.len() / 2;
+        nodes.select_nth_unstable_by(middle, |&a, &b| axis(points[a], depth).total_cmp(&axis(points[b], depth)));
+        let (left, right) = nodes.split_at_mut(middle);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Closest point that is not exactly at `point`, with its distance.
+    /// Of several equally close points, the one with the lowest index is returned.
+    pub fn nearest(&self, point: Vector2F) -> Option<(usize, f64)> {
+        let mut best: Option<(usize, f64)> = None;
+        let bounds: [Vector2F; 2] = [Vector2F::new(f64::MIN, f64::MIN), Vector2F::new(f64::MAX, f64::MAX)];
+        self.search_nearest(&self.nodes, 0, bounds, point, &mut best);
+        best
+    }
+
+    /// `bounds` is the box (minimum and maximum corner) that contains all points of `nodes`;
+    /// subtrees whose box is farther away than the best match so far are skipped.
+    fn search_nearest(&self, nodes: &[usize], depth: usize, bounds: [Vector2F; 2], point: Vector2F, best: &mut Option<(usize, f64)>) {
+        if nodes.is_empty() {
+            return;
+        }
+        let outside: Vector2F = Vector2F::new((bounds[0].x - point.x).max(point.x - bounds[1].x).max(0.0), (bounds[0].y - point.y).max(point.y - bounds[1].y).max(0.0));
+        if best.is_some_and(|(_, best_distance)| outside.abs() > best_distance) {
+            return;
+        }
+        let middle: usize = nodes.len() / 2;
+        let node: usize = nodes[middle];
+        let distance: f64 = point.distance(self.points[node]);
+        let better: bool = match best {
+            Some((index, best_distance)) => distance < *best_distance || (distance == *best_distance && node < *index),
+            None => true,
+        };
+        if distance != 0.0 && better {
+            *best = Some((node, distance));
+        }
+        let split: f64 = axis(self.points[node], depth);
+        let (mut lower, mut upper) = (bounds, bounds);
+        if depth.is_multiple_of(2) {
+            lower[1].x = split;
+            upper[0].x = split;
+        } else {
+            lower[1].y = split;
+            upper[0].y = split;
+        }
+        let lower: (&[usize], [Vector2F; 2]) = (&nodes[..middle], lower);
+        let upper: (&[usize], [Vector2F; 2]) = (&nodes[middle+1..], upper);
+        let (near, far) = if axis(point, depth) < split { (lower, upper) } else { (upper, lower) };
+        self.search_nearest(near.0, depth + 1, near.1, point, best);
+        self.search_nearest(far.0, depth + 1, far.1, point, best);
+    }
+
+    /// All points within `radius` of `point` (inclusive), as sorted indices.
+    pub fn within_radius(&self, point: Vector2F, radius: f64) -> Vec<usize> {
+        let mut found: Vec<usize> = Vec::new();
+        self.search_radius(&self.nodes, 0, point, radius, &mut found);
+        found.sort_unstable();
+        found
+    }
+
+    fn search_radius(&self, nodes: &[usize], depth: usize, point: Vector2F, radius: f64, found: &mut Vec<usize>) {
+        if nodes.is_empty() {
+            return;
+        }
+        let middle: usize = nodes.len() / 2;
+        let node: usize = nodes[middle];
+        if point.distance(self.points[node]) <= radius {
+            found.push(node);
+        }
+        let difference: f64 = axis(point, depth) - axis(self.points[node], depth);
+        if difference <= radius {
+            self.search_radius(&nodes[..middle], depth + 1, point, radius, found);
+        }
+        if difference >= -radius {
+            self.search_radius(&nodes[middle+1..], depth + 1, point, radius, found);
+        }
+    }
+}
+
+impl Shape {
+    /// Builds a [`KdTree`] over `coordinates`. Build it once and reuse it for repeated lookups.
+    pub fn spatial_index(&self) -> KdTree {
+        KdTree::new(&self.coordinates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::ShapeName;
+
+    #[test]
+    fn test_queries_match_linear_scan() {
+        let mut seed: u64 = 11;
+        let mut coordinates: Vec<Vector2D> = Vec::new();
+        for _ in 0..500 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            coordinates.push(Vector2D { x: (seed >> 33) as i32 % 100, y: (seed >> 45) as i32 % 100 });
+        }
+        let shape: Shape = Shape { coordinates: coordinates.clone(), shape_type: ShapeName::Undefined };
+        let index: KdTree = shape.spatial_index();
+        for query in [Vector2F::new(50.0, 50.0), Vector2F::new(-20.0, 130.5), coordinates[17].to_f64(), Vector2F::new(33.3, 66.6)] {
+            let (closest, distance) = shape.get_closest_to_point(query);
+            let (nearest, nearest_distance) = index.nearest(query).unwrap();
+            assert_eq!((coordinates[nearest], nearest_distance), (closest, distance));
+            let expected: Vec<usize> = (0..coordinates.len()).filter(|&i| query.distance(coordinates[i].to_f64()) <= 15.0).collect();
+            assert_eq!(index.within_radius(query, 15.0), expected);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use crate::curvature::{resample, Corner};
+use crate::shape::Shape;
+use crate::vector::Vector2F;
+
+/// Number of points the stroke is resampled to before searching for self-intersections.
+const INTERSECTION_RESAMPLE_POINTS: usize = 128;
+
+/// A star drawn in one stroke, either as a pentagram-like star polygon or as its outline.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Star {
+    pub points: usize,
+    /// Mean of the tips.
+    pub centre: Vector2F,
+    /// Mean distance of the tips to the centre.
+    pub radius: f64,
+    /// Angle of the first tip as seen from the centre, in radians.
+    pub rotation: f64,
+    pub self_intersections: usize,
+}
+
+/// The point where the segments `a`–`b` and `c`–`d` cross, if they do; touching does not count.
+pub fn segment_intersection(a: Vector2F, b: Vector2F, c: Vector2F, d: Vector2F) -> Option<Vector2F> {
+    let (ab, cd) = (b - a, d - c);
+    let denominator: f64 = ab.cross(cd);
+    if denominator == 0.0 {
+        return None;
+    }
+    let t: f64 = (c - a).cross(cd) / denominator;
+    let u: f64 = (c - a).cross(ab) / denominator;
+    if t > 0.0 && t < 1.0 && u > 0.0 && u < 1.0 {
+        Some(a.lerp(b, t))
+    } else {
+        None
+    }
+}
+
+impl Shape {
+    /// Points where the stroke crosses itself, in drawing order of the earlier of the two crossing parts.
+    /// Computed on an evenly resampled copy, so that jitter of the cursor does not count as a crossing.
+    pub fn self_intersections(&self) -> Vec<Vector2F> {
+        let points: Vec<Vector2F> = resample(&self.coordinates, INTERSECTION_RESAMPLE_POINTS).into_iter().map(|(point, _)| point).collect();
+        let mut intersections: Vec<Vector2F> = Vec::new();
+        for i in 0..points.len().saturating_sub(1) {
+            for j in i + 2..points.len() - 1 {
+                intersections.extend(segment_intersection(points[i], points[i + 1], points[j], points[j + 1]));
+            }
+        }
+        intersections
+    }
+
+    /// Recognizes a closed stroke as a star. A star polygon such as the pentagram turns the same way
+    /// at all of its tips and winds around its centre twice; an outlined star alternates between
+    /// tips and inner corners turning the other way and winds around once. Stars need at least 5 tips.
+    /// The corner where the stroke starts and ends counts as well, since strokes usually start at a tip.
+    pub fn star(&self) -> Option<Star> {
+        self.star_with_score().map(|(star, _)| star)
+    }
+
+    /// The star and how evenly its tips lie on a circle, in percent.
+    pub(crate) fn star_with_score(&self) -> Option<(Star, f64)> {
+        let (corners, total_turning) = self.closed_corners()?;
+        let turns: i64 = (total_turning / TAU).round() as i64;
+        let tips: Vec<Corner> = match turns.abs() {
+            2 if corners.iter().all(|corner| corner.angle.signum() == total_turning.signum() && corner.angle.abs() >= FRAC_PI_2) => corners,
+            1 if corners.len().is_multiple_of(2) && corners.windows(2).all(|pair| pair[0].angle.signum() != pair[1].angle.signum()) => {
+                corners.into_iter().filter(|corner| corner.angle.signum() == total_turning.signum()).collect()
+            },
+            _ => return None,
+        };
+        if tips.len() < 5 {
+            return None;
+        }
+        let centre: Vector2F = tips.iter().fold(Vector2F::default(), |sum, tip| sum + tip.point.to_f64()) / tips.len() as f64;
+        let radius: f64 = tips.iter().map(|tip| tip.point.to_f64().distance(centre)).sum::<f64>() / tips.len() as f64;
+        let deviation: f64 = tips.iter().map(|tip| (tip.point.to_f64().distance(centre) - radius).abs()).sum::<f64>() / tips.len() as f64;
+        let score: f64 = if radius > 0.0 { ((1.0 - deviation / radius) * 100.0).max(0.0) } else { 0.0 };
+        Some((Star { points: tips.len(), centre, radius, rotation: (tips[0].point.to_f64() - centre).angle(), self_intersections: self.self_intersections().len() }, score))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::fixtures::polyline_through;
+    use crate::shape::{Classification, ShapeName};
+
+    /// Straight lines through `corners`, closed back to the first corner.
+    fn closed_polyline(corners: &[Vector2F]) -> Shape {
+        polyline_through(&[corners, &corners[..1]].concat())
+    }
+
+    #[test]
+    fn test_stars() {
+        let centre: Vector2F = Vector2F::new(500.0, 400.0);
+        let tip = |i: usize, radius: f64| centre + Vector2F::new(0.0, -radius).rotate(TAU * i as f64 / 10.0);
+        let pentagram: Shape = closed_polyline(&(0..5).map(|i| tip(4 * i % 10, 200.0)).collect::<Vec<Vector2F>>());
+        let classification: Classification = pentagram.classify();
+        assert_eq!(classification.shape_name, ShapeName::Star);
+        let star: Star = classification.star.unwrap();
+        assert_eq!((star.points, star.self_intersections), (5, 5));
+        assert!(star.centre.distance(centre) < 2.0 && (star.radius - 200.0).abs() < 2.0);
+        assert_eq!(classification.to_string(), "STAR (99%, 5 points)");
+
+        let outline: Shape = closed_polyline(&(0..10).rev().map(|i| tip(i, if i % 2 == 0 { 200.0 } else { 80.0 })).collect::<Vec<Vector2F>>());
+        let star: Star = outline.star().unwrap();
+        assert_eq!((star.points, star.self_intersections), (5, 0));
+        assert_eq!(outline.classify().winding, Some(crate::direction::Winding::CounterClockwise));
+
+        let square: Shape = closed_polyline(&[Vector2F::new(100.0, 100.0), Vector2F::new(300.0, 100.0), Vector2F::new(300.0, 300.0), Vector2F::new(100.0, 300.0)]);
+        assert_eq!(square.star(), None);
+        assert_eq!(segment_intersection(Vector2F::new(0.0, 0.0), Vector2F::new(2.0, 2.0), Vector2F::new(0.0, 2.0), Vector2F::new(2.0, 0.0)), Some(Vector2F::new(1.0, 1.0)));
+    }
+}
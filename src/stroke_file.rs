@@ -0,0 +1,74 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Plain-text recording files: one stroke per line, written as space-separated `x,y` pairs.
+//! Empty lines and lines starting with `#` are ignored.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::vector::Vector2D;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn parse_strokes(text: &str) -> Result<Vec<Vec<Vector2D>>, ParseError> {
+    let mut strokes: Vec<Vec<Vector2D>> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line: &str = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut stroke: Vec<Vector2D> = Vec::new();
+        for pair in line.split_whitespace() {
+            let error = || ParseError { line: number + 1, message: format!("expected a coordinate like `12,34`, found `{}`", pair) };
+            let (x, y) = pair.split_once(',').ok_or_else(error)?;
+            stroke.push(Vector2D { x: x.parse().map_err(|_| error())?, y: y.parse().map_err(|_| error())? });
+        }
+        strokes.push(stroke);
+    }
+    Ok(strokes)
+}
+
+/// Formats one stroke as a line of a recording file, without the line break.
+pub fn format_stroke(coordinates: &[Vector2D]) -> String {
+    coordinates.iter().map(|coordinate| format!("{},{}", coordinate.x, coordinate.y)).collect::<Vec<String>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_strokes() {
+        let strokes: Vec<Vec<Vector2D>> = vec![vec![Vector2D { x: 1, y: 2 }, Vector2D { x: -3, y: 40 }], vec![Vector2D { x: 5, y: 6 }]];
+        let text: String = format!("# recorded strokes\n{}\n\n{}\n", format_stroke(&strokes[0]), format_stroke(&strokes[1]));
+        assert_eq!(parse_strokes(&text), Ok(strokes));
+        assert_eq!(parse_strokes("1,2 3;4").unwrap_err().line, 1);
+    }
+}
@@ -0,0 +1,115 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::Write;
+
+use crate::primitive::Primitive;
+use crate::shape::{Classification, Shape};
+use crate::vector::Vector2F;
+use crate::{CIRCLE_TOLERANCE, LINE_TOLERANCE_PX};
+
+const MARGIN_PX: f64 = 40.0;
+const STROKE_COLOUR: &str = "#1f3a93";
+const OVERLAY_COLOUR: &str = "#d35400";
+const BAND_COLOUR: &str = "#27ae60";
+
+fn point(point: Vector2F) -> String {
+    format!("{:.1},{:.1}", point.x, point.y)
+}
+
+/// Escapes the characters that would end or break the text of an element.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Renders the stroke as an SVG document for debugging the recognizer.
+/// Overlays the centre, the farthest pair of points, the circle and line tolerance bands,
+/// the fitted primitive and a label with the classification.
+pub fn render_svg(shape: &Shape) -> String {
+    let points: Vec<Vector2F> = shape.coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+    let mut min: Vector2F = Vector2F::new(f64::MAX, f64::MAX);
+    let mut max: Vector2F = Vector2F::new(f64::MIN, f64::MIN);
+    let mut include = |point: Vector2F, padding: f64| {
+        min = Vector2F::new(min.x.min(point.x - padding), min.y.min(point.y - padding));
+        max = Vector2F::new(max.x.max(point.x + padding), max.y.max(point.y + padding));
+    };
+    for point in &points {
+        include(*point, LINE_TOLERANCE_PX);
+    }
+    let mut body: String = String::new();
+    let label: String = if points.is_empty() {
+        include(Vector2F::default(), 0.0);
+        String::from("EMPTY")
+    } else {
+        let classification: Classification = shape.classify();
+        let centre: Vector2F = shape.find_centre();
+        let average: f64 = shape.get_point_distances(centre).avg;
+        include(centre, average * (1.0 + CIRCLE_TOLERANCE));
+        let _ = writeln!(body, r#"  <circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="none" stroke="{}" stroke-opacity="0.2" stroke-width="{:.1}"/>"#, centre.x, centre.y, average, BAND_COLOUR, 2.0 * CIRCLE_TOLERANCE * average);
+        let (start, end) = (points[0], points[points.len()-1]);
+        let direction: Vector2F = (end - start).normalize();
+        if direction != Vector2F::default() {
+            let offset: Vector2F = Vector2F::new(-direction.y, direction.x) * LINE_TOLERANCE_PX;
+            let _ = writeln!(body, r#"  <polygon points="{} {} {} {}" fill="{}" fill-opacity="0.2"/>"#, point(start + offset), point(end + offset), point(end - offset), point(start - offset), BAND_COLOUR);
+        }
+        let _ = writeln!(body, r#"  <polyline points="{}" fill="none" stroke="{}" stroke-width="2" stroke-linejoin="round"/>"#, points.iter().map(|point| self::point(*point)).collect::<Vec<String>>().join(" "), STROKE_COLOUR);
+        let max_pair: [Vector2F; 2] = shape.get_distances().max_pair;
+        let _ = writeln!(body, r#"  <line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-dasharray="4 4"/>"#, max_pair[0].x, max_pair[0].y, max_pair[1].x, max_pair[1].y, OVERLAY_COLOUR);
+        match shape.fit_primitive(&classification.candidate) {
+            Some(Primitive::Circle { centre, radius }) => {
+                let _ = writeln!(body, r#"  <circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="none" stroke="{}" stroke-width="2" stroke-dasharray="8 4"/>"#, centre.x, centre.y, radius, OVERLAY_COLOUR);
+            },
+            Some(Primitive::Ellipse { centre, radii, rotation }) => {
+                include(centre, radii.x);
+                let _ = writeln!(body, r#"  <ellipse cx="{:.1}" cy="{:.1}" rx="{:.1}" ry="{:.1}" transform="rotate({:.2} {:.1} {:.1})" fill="none" stroke="{}" stroke-width="2" stroke-dasharray="8 4"/>"#, centre.x, centre.y, radii.x, radii.y, rotation.to_degrees(), centre.x, centre.y, OVERLAY_COLOUR);
+            },
+            Some(Primitive::Line { start, end }) => {
+                let _ = writeln!(body, r#"  <line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="2" stroke-dasharray="8 4"/>"#, start.x, start.y, end.x, end.y, OVERLAY_COLOUR);
+            },
+            Some(primitive @ Primitive::Star { .. }) => {
+                let _ = writeln!(body, r#"  <polygon points="{}" fill="none" stroke="{}" stroke-width="2" stroke-dasharray="8 4"/>"#, primitive.outline(0).iter().map(|point| self::point(*point)).collect::<Vec<String>>().join(" "), OVERLAY_COLOUR);
+            },
+            None => (),
+        }
+        let _ = writeln!(body, r#"  <circle cx="{:.1}" cy="{:.1}" r="4" fill="{}"/>"#, centre.x, centre.y, OVERLAY_COLOUR);
+        classification.to_string()
+    };
+    let (origin, size) = (min - Vector2F::new(MARGIN_PX, 2.0 * MARGIN_PX), max - min + Vector2F::new(2.0 * MARGIN_PX, 3.0 * MARGIN_PX));
+    let mut svg: String = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{:.1} {:.1} {:.1} {:.1}" width="{:.0}" height="{:.0}">"#, origin.x, origin.y, size.x, size.y, size.x, size.y);
+    let _ = writeln!(svg, r#"  <rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="white"/>"#, origin.x, origin.y, size.x, size.y);
+    svg.push_str(&body);
+    let _ = writeln!(svg, r#"  <text x="{:.1}" y="{:.1}" font-family="monospace" font-size="20">{}</text>"#, origin.x + MARGIN_PX / 2.0, origin.y + MARGIN_PX, escape(&label));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::ShapeName;
+    use crate::vector::Vector2D;
+
+    #[test]
+    fn test_render_svg() {
+        let coordinates: Vec<Vector2D> = (0..50).map(|i| Vector2D { x: 100 + i * 10, y: 200 + i * 2 }).collect();
+        let svg: String = render_svg(&Shape { coordinates, shape_type: ShapeName::Undefined });
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains("<polyline ") && svg.contains("<polygon ") && svg.contains(">LINE (100%, right, 500 px)</text>"));
+        assert!(render_svg(&Shape { coordinates: Vec::new(), shape_type: ShapeName::Undefined }).contains(">EMPTY</text>"));
+        assert_eq!(escape("<a> & <b>"), "&lt;a&gt; &amp; &lt;b&gt;");
+    }
+}
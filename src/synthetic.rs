@@ -0,0 +1,215 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Strokes drawn by a program instead of a hand, for tests, benchmarks and labelled datasets.
+//!
+//! A [`Figure`] is a curve over the parameter 0 to 1. The [`Generator`] samples it and adds what makes
+//! a hand-drawn stroke imperfect: noise on every point, a start somewhere else on the figure and a
+//! stroke that goes on a bit after it should have ended. The same seed always gives the same strokes.
+
+use std::f64::consts::TAU;
+use std::time::Duration;
+
+use crate::shape::{Shape, ShapeName};
+use crate::vector::{Vector2D, Vector2F};
+
+/// A curve the [`Generator`] can draw; `size` and `rotation` are set on the generator.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Figure {
+    Circle,
+    /// `aspect` is the minor axis divided by the major one.
+    Ellipse { aspect: f64 },
+    Line,
+    /// A regular polygon whose corners lie on a circle.
+    Polygon { sides: usize },
+    /// Part of a circle; a negative `sweep` (in radians) draws it the other way round.
+    Arc { sweep: f64 },
+    /// Starts in the centre and grows to the full size in `turns` turns.
+    Spiral { turns: f64 },
+}
+
+/// How strokes are drawn. All lengths are in pixels.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Generator {
+    pub centre: Vector2F,
+    /// Diameter of circles, arcs, spirals and the circle around polygons; major axis of ellipses;
+    /// length of lines.
+    pub size: f64,
+    /// Radians, counter-clockwise in a y-up coordinate system. Line 0 points to the right.
+    pub rotation: f64,
+    /// Number of points of a stroke.
+    pub samples: usize,
+    /// Standard deviation of the Gaussian noise added to every point.
+    pub noise: f64,
+    /// The stroke starts up to this fraction of the figure before or after its usual start.
+    pub start_jitter: f64,
+    /// Fraction of the figure drawn again after its end.
+    pub overshoot: f64,
+    pub speed_px_per_s: f64,
+    /// State of the random number generator.
+    pub seed: u64,
+}
+
+/// A generated stroke with the time each point was drawn at, counted from the first one.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SyntheticStroke {
+    pub coordinates: Vec<Vector2D>,
+    pub timestamps: Vec<Duration>,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Generator { centre: Vector2F::new(1000.0, 600.0), size: 400.0, rotation: 0.0, samples: 100, noise: 0.0, start_jitter: 0.0, overshoot: 0.0, speed_px_per_s: 1000.0, seed: 1 }
+    }
+}
+
+impl Figure {
+    /// The point at `t` of a figure with diameter 1 around the origin; values of `t` outside of 0 to 1
+    /// continue the figure.
+    fn point(&self, t: f64) -> Vector2F {
+        match *self {
+            Figure::Circle => Vector2F::new(0.5, 0.0).rotate(TAU * t),
+            Figure::Ellipse { aspect } => Vector2F::new(0.5 * (TAU * t).cos(), 0.5 * aspect * (TAU * t).sin()),
+            Figure::Line => Vector2F::new(t - 0.5, 0.0),
+            Figure::Polygon { sides } => {
+                let sides: f64 = sides.max(1) as f64;
+                let edge: f64 = (t * sides).floor();
+                let corner = |index: f64| Vector2F::new(0.5, 0.0).rotate(TAU * index / sides);
+                corner(edge).lerp(corner(edge + 1.0), t * sides - edge)
+            }
+            Figure::Arc { sweep } => Vector2F::new(0.5, 0.0).rotate(sweep * t),
+            Figure::Spiral { turns } => Vector2F::new(0.5 * t.max(0.0), 0.0).rotate(TAU * turns * t),
+        }
+    }
+}
+
+impl Generator {
+    /// Uniformly distributed between 0 (exclusive) and 1 (inclusive).
+    fn uniform(&mut self) -> f64 {
+        self.seed = self.seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.seed >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Normally distributed with mean 0 and standard deviation 1 (Box-Muller transform).
+    fn gaussian(&mut self) -> f64 {
+        (-2.0 * self.uniform().ln()).sqrt() * (TAU * self.uniform()).cos()
+    }
+
+    /// Draws `figure` once; every call continues the random sequence, so repeated calls give
+    /// different strokes.
+    pub fn stroke(&mut self, figure: Figure) -> SyntheticStroke {
+        self.trace(|t| figure.point(t))
+    }
+
+    /// Like [`Generator::stroke`], for any curve of about size 1 around the origin. It is called with
+    /// parameters from 0 to 1, and outside of that for the start jitter and the overshoot.
+    pub fn trace(&mut self, curve: impl Fn(f64) -> Vector2F) -> SyntheticStroke {
+        let start: f64 = (self.uniform() * 2.0 - 1.0) * self.start_jitter;
+        let span: f64 = 1.0 + self.overshoot;
+        let intervals: f64 = self.samples.saturating_sub(1).max(1) as f64;
+        let ideal: Vec<Vector2F> = (0..self.samples).map(|i| self.centre + (curve(start + span * i as f64 / intervals) * self.size).rotate(self.rotation)).collect();
+        let mut timestamps: Vec<Duration> = Vec::with_capacity(ideal.len());
+        let mut length: f64 = 0.0;
+        for (i, point) in ideal.iter().enumerate() {
+            if i > 0 {
+                length += point.distance(ideal[i - 1]);
+            }
+            timestamps.push(Duration::try_from_secs_f64(length / self.speed_px_per_s).unwrap_or(Duration::MAX));
+        }
+        let coordinates: Vec<Vector2D> = ideal.into_iter().map(|point| (point + Vector2F::new(self.gaussian(), self.gaussian()) * self.noise).round()).collect();
+        SyntheticStroke { coordinates, timestamps }
+    }
+
+    /// Like [`Generator::stroke`], without the timestamps.
+    pub fn points(&mut self, figure: Figure) -> Vec<Vector2D> {
+        self.stroke(figure).coordinates
+    }
+}
+
+impl SyntheticStroke {
+    /// How long drawing the stroke took, for [`Classifier::classify_timed`](crate::recognizer::Classifier::classify_timed).
+    pub fn duration(&self) -> Option<Duration> {
+        self.timestamps.last().copied()
+    }
+
+    pub fn shape(&self) -> Shape {
+        Shape { coordinates: self.coordinates.clone(), shape_type: ShapeName::Undefined }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluation::{evaluate, Evaluation, Sample};
+    use crate::recognizer::Classifier;
+
+    #[test]
+    fn test_synthetic() {
+        let mut generator: Generator = Generator { seed: 3, ..Generator::default() };
+        let circle: SyntheticStroke = generator.stroke(Figure::Circle);
+        assert_eq!((circle.coordinates.len(), circle.timestamps.len()), (100, 100));
+        assert_eq!(circle.coordinates[0], circle.coordinates[99]);
+        assert!(circle.shape().bounding_box().width() <= 400.0);
+        // The circumference of 1257 px at 1000 px/s.
+        assert!((circle.duration().unwrap().as_secs_f64() - 1.257).abs() < 0.01, "{:?}", circle.duration());
+        assert!(circle.timestamps.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(Generator { seed: 3, ..Generator::default() }.stroke(Figure::Circle), circle);
+
+        let line: Vec<Vector2D> = Generator { rotation: TAU / 4.0, overshoot: 0.5, ..Generator::default() }.points(Figure::Line);
+        assert_eq!((line[0], line[99]), (Vector2D { x: 1000, y: 400 }, Vector2D { x: 1000, y: 1000 }));
+        let square: Vec<Vector2D> = Generator { samples: 5, ..Generator::default() }.points(Figure::Polygon { sides: 4 });
+        assert_eq!(square[1], Vector2D { x: 1000, y: 800 });
+
+        // Noise of up to a pixel must not change what any of the strokes is recognized as, whether an
+        // ellipse is flat or nearly round.
+        let classifier: Classifier = Classifier::default();
+        let mut figures: Vec<(Figure, ShapeName)> = vec![(Figure::Circle, ShapeName::Circle), (Figure::Line, ShapeName::Line)];
+        figures.extend([0.3, 0.4, 0.5, 0.6, 0.7, 0.8].map(|aspect| (Figure::Ellipse { aspect }, ShapeName::Ellipse)));
+        for noise in [0.0, 0.5, 1.0] {
+            let mut generator: Generator = Generator { noise, start_jitter: 0.5, overshoot: 0.05, ..Generator::default() };
+            for (figure, expected) in &figures {
+                for _ in 0..10 {
+                    generator.rotation = generator.uniform() * TAU;
+                    let stroke: SyntheticStroke = generator.stroke(*figure);
+                    assert_eq!(&classifier.classify_timed(&stroke.shape(), stroke.duration()).candidate, expected, "{:?} with noise {}", figure, noise);
+                }
+            }
+        }
+        // Hand-like noise of 3 to 5 px still leaves nine in ten strokes of every class, and of the
+        // ellipses of every aspect.
+        for noise in [3.0, 4.0, 5.0] {
+            let mut generator: Generator = Generator { noise, start_jitter: 0.5, overshoot: 0.05, ..Generator::default() };
+            let mut samples: Vec<Sample> = Vec::new();
+            for (figure, label) in &figures {
+                let mut ellipses: Vec<Sample> = Vec::new();
+                for _ in 0..20 {
+                    generator.rotation = generator.uniform() * TAU;
+                    ellipses.push(Sample { label: label.clone(), coordinates: generator.points(*figure) });
+                }
+                if let Figure::Ellipse { aspect } = figure {
+                    let evaluation: Evaluation = evaluate(&classifier, &ellipses);
+                    assert!(evaluation.recall(&ShapeName::Ellipse) >= Some(0.9), "aspect {} with noise {}\n{}", aspect, noise, evaluation);
+                }
+                samples.extend(ellipses);
+            }
+            let evaluation: Evaluation = evaluate(&classifier, &samples);
+            assert!(evaluation.accuracy() >= 0.9, "noise {}\n{}", noise, evaluation);
+            for name in [ShapeName::Circle, ShapeName::Ellipse, ShapeName::Line] {
+                assert!(evaluation.recall(&name) >= Some(0.9), "{} with noise {}\n{}", name, noise, evaluation);
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::primitive::Primitive;
+use crate::vector::{Vector2D, Vector2F};
+
+const OVERLAY_COLOUR: &str = "\x1b[38;5;208m";
+const RESET_COLOUR: &str = "\x1b[0m";
+
+/// A monochrome canvas made of braille characters, each holding 2 x 4 dots.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BrailleCanvas {
+    columns: usize,
+    rows: usize,
+    stroke: Vec<u8>,
+    overlay: Vec<u8>,
+}
+
+impl BrailleCanvas {
+    pub fn new(columns: usize, rows: usize) -> Self {
+        BrailleCanvas { columns, rows, stroke: vec![0; columns * rows], overlay: vec![0; columns * rows] }
+    }
+
+    /// Size in dots.
+    pub fn size(&self) -> (usize, usize) {
+        (self.columns * 2, self.rows * 4)
+    }
+
+    fn set(&mut self, x: i64, y: i64, overlay: bool) {
+        let (width, height) = self.size();
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        // Dot numbering of the Unicode braille patterns.
+        const BITS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+        let cell: usize = (y / 4) * self.columns + x / 2;
+        let layer: &mut Vec<u8> = if overlay { &mut self.overlay } else { &mut self.stroke };
+        layer[cell] |= BITS[x % 2][y % 4];
+    }
+
+    /// Draws a line between two dot positions.
+    pub fn draw_line(&mut self, start: Vector2F, end: Vector2F, overlay: bool) {
+        let steps: usize = (end - start).x.abs().max((end - start).y.abs()).ceil().min(100_000.0) as usize;
+        for step in 0..=steps {
+            let point: Vector2F = if steps == 0 { start } else { start.lerp(end, step as f64 / steps as f64) };
+            self.set(point.x.round() as i64, point.y.round() as i64, overlay);
+        }
+    }
+
+    pub fn draw_polyline(&mut self, points: &[Vector2F], overlay: bool) {
+        for pair in points.windows(2) {
+            self.draw_line(pair[0], pair[1], overlay);
+        }
+        if let [point] = points {
+            self.draw_line(*point, *point, overlay);
+        }
+    }
+
+    /// One string per row; cells that only contain overlay dots are coloured.
+    pub fn lines(&self) -> Vec<String> {
+        (0..self.rows).map(|row| {
+            let mut line: String = String::new();
+            for column in 0..self.columns {
+                let cell: usize = row * self.columns + column;
+                let dots: u8 = self.stroke[cell] | self.overlay[cell];
+                let character: char = char::from_u32(0x2800 + dots as u32).unwrap_or(' ');
+                if self.stroke[cell] == 0 && self.overlay[cell] != 0 {
+                    line.push_str(OVERLAY_COLOUR);
+                    line.push(character);
+                    line.push_str(RESET_COLOUR);
+                } else {
+                    line.push(character);
+                }
+            }
+            line
+        }).collect()
+    }
+}
+
+/// Draws a stroke (and optionally a fitted primitive) scaled to fit a canvas of the given size
+/// in characters, keeping the aspect ratio of the screen.
+pub fn draw_stroke(coordinates: &[Vector2D], primitive: Option<Primitive>, columns: usize, rows: usize) -> BrailleCanvas {
+    let mut canvas: BrailleCanvas = BrailleCanvas::new(columns, rows);
+    if coordinates.is_empty() || columns == 0 || rows == 0 {
+        return canvas;
+    }
+    let points: Vec<Vector2F> = coordinates.iter().map(|coordinate| coordinate.to_f64()).collect();
+    let mut min: Vector2F = Vector2F::new(f64::MAX, f64::MAX);
+    let mut max: Vector2F = Vector2F::new(f64::MIN, f64::MIN);
+    for point in &points {
+        min = Vector2F::new(min.x.min(point.x), min.y.min(point.y));
+        max = Vector2F::new(max.x.max(point.x), max.y.max(point.y));
+    }
+    let (width, height) = canvas.size();
+    let scale: f64 = ((width as f64 - 2.0) / (max.x - min.x).max(1.0)).min((height as f64 - 2.0) / (max.y - min.y).max(1.0));
+    let middle: Vector2F = (min + max) / 2.0;
+    let to_canvas = |point: Vector2F| (point - middle) * scale + Vector2F::new(width as f64 / 2.0, height as f64 / 2.0);
+    if let Some(primitive) = primitive {
+        canvas.draw_polyline(&primitive.outline(64).into_iter().map(to_canvas).collect::<Vec<Vector2F>>(), true);
+    }
+    canvas.draw_polyline(&points.into_iter().map(to_canvas).collect::<Vec<Vector2F>>(), false);
+    canvas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_stroke() {
+        let coordinates: Vec<Vector2D> = vec![Vector2D { x: 0, y: 0 }, Vector2D { x: 100, y: 0 }];
+        let lines: Vec<String> = draw_stroke(&coordinates, None, 10, 3).lines();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "\u{2800}".repeat(10));
+        assert_eq!(lines[1], format!("\u{2820}{}", "\u{2824}".repeat(9)));
+        let primitive: Primitive = Primitive::Line { start: Vector2F::new(0.0, 20.0), end: Vector2F::new(100.0, 20.0) };
+        assert!(draw_stroke(&coordinates, Some(primitive), 10, 3).lines()[2].contains(OVERLAY_COLOUR));
+    }
+}
@@ -0,0 +1,265 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+
+/// A cursor coordinate in screen pixels, as captured from the mouse.
+#[derive(Clone, Default, PartialEq, Eq, Copy, Debug)]
+pub struct Vector2D {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// A point or direction with floating-point components, used for all recognition maths.
+#[derive(Clone, Default, PartialEq, Copy, Debug)]
+pub struct Vector2F {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vector2D {
+    pub fn abs(&self) -> f32 {
+        self.to_f64().abs() as f32
+    }
+
+    /// Exact for all coordinates, unlike the `i32` operators, which saturate; that is why it is 64 bits
+    /// wide.
+    pub fn cross(self, other: Vector2D) -> i64 {
+        self.x as i64 * other.y as i64 - self.y as i64 * other.x as i64
+    }
+
+    pub fn distance(&self, other: &Vector2D) -> i32 {
+        self.to_f64().distance(other.to_f64()) as i32
+    }
+
+    pub fn distance_to_line(self, b: Vector2D, c: Vector2D) -> f32 {
+        self.to_f64().distance_to_line(b.to_f64(), c.to_f64()) as f32
+    }
+
+    pub fn to_f64(self) -> Vector2F {
+        Vector2F { x: self.x as f64, y: self.y as f64 }
+    }
+}
+
+impl Vector2F {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// Length of the vector.
+    pub fn abs(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    pub fn dot(self, other: Vector2F) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product; positive if `other` is counter-clockwise of `self`
+    /// in a y-up coordinate system (clockwise on screen, where y points down).
+    pub fn cross(self, other: Vector2F) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn distance(self, other: Vector2F) -> f64 {
+        (self - other).abs()
+    }
+
+    /// Signed distance of `self` to the infinite line through `b` and `c`.
+    /// Falls back to the distance to `b` if both points are the same.
+    pub fn distance_to_line(self, b: Vector2F, c: Vector2F) -> f64 {
+        let length: f64 = (b - c).abs();
+        if length == 0.0 {
+            return self.distance(b);
+        }
+        (self - b).cross(self - c) / length
+    }
+
+    /// Unit vector in the same direction; the zero vector stays zero.
+    pub fn normalize(self) -> Vector2F {
+        let length: f64 = self.abs();
+        if length == 0.0 {
+            self
+        } else {
+            self / length
+        }
+    }
+
+    /// Rotates the vector by `angle` radians around the origin.
+    pub fn rotate(self, angle: f64) -> Vector2F {
+        let (sin, cos) = angle.sin_cos();
+        Vector2F { x: self.x * cos - self.y * sin, y: self.x * sin + self.y * cos }
+    }
+
+    /// Linear interpolation; `t = 0` returns `self`, `t = 1` returns `other`.
+    pub fn lerp(self, other: Vector2F, t: f64) -> Vector2F {
+        self + (other - self) * t
+    }
+
+    /// Angle of the vector against the positive x axis in radians, in `(-π, π]`.
+    pub fn angle(self) -> f64 {
+        self.y.atan2(self.x)
+    }
+
+    /// Signed angle in radians needed to rotate `self` onto `other`, in `(-π, π]`.
+    pub fn angle_to(self, other: Vector2F) -> f64 {
+        self.cross(other).atan2(self.dot(other))
+    }
+
+    /// Rounds to the nearest pixel coordinate.
+    pub fn round(self) -> Vector2D {
+        Vector2D { x: self.x.round() as i32, y: self.y.round() as i32 }
+    }
+}
+
+impl From<Vector2D> for Vector2F {
+    fn from(vector: Vector2D) -> Self {
+        vector.to_f64()
+    }
+}
+
+impl Sub for Vector2D {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            x: self.x.saturating_sub(other.x),
+            y: self.y.saturating_sub(other.y),
+        }
+    }
+}
+
+impl Add for Vector2D {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            x: self.x.saturating_add(other.x),
+            y: self.y.saturating_add(other.y),
+        }
+    }
+}
+
+impl Mul<i32> for Vector2D {
+    type Output = Self;
+
+    fn mul(self, other: i32) -> Self::Output {
+        Self {
+            x: self.x.saturating_mul(other),
+            y: self.y.saturating_mul(other),
+        }
+    }
+}
+
+impl Mul for Vector2D {
+    type Output = i32;
+
+    fn mul(self, other: Self) -> Self::Output {
+        (self.x as i128 * other.x as i128 + self.y as i128 * other.y as i128).clamp(i32::MIN as i128, i32::MAX as i128) as i32
+    }
+}
+
+impl Sub for Vector2F {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl Add for Vector2F {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl AddAssign for Vector2F {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl Neg for Vector2F {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self { x: -self.x, y: -self.y }
+    }
+}
+
+impl Div<f64> for Vector2F {
+    type Output = Self;
+
+    fn div(self, other: f64) -> Self::Output {
+        Self {
+            x: self.x / other,
+            y: self.y / other,
+        }
+    }
+}
+
+impl Mul<f64> for Vector2F {
+    type Output = Self;
+
+    fn mul(self, other: f64) -> Self::Output {
+        Self {
+            x: self.x * other,
+            y: self.y * other,
+        }
+    }
+}
+
+impl Mul for Vector2F {
+    type Output = f64;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.dot(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_vector2f_operations() {
+        let a: Vector2F = Vector2F::new(3.0, 4.0);
+        let b: Vector2F = Vector2F::new(-4.0, 3.0);
+        assert_eq!(a.abs(), 5.0);
+        assert_eq!(a.dot(b), 0.0);
+        assert_eq!(a * b, 0.0);
+        assert_eq!(a.cross(b), 25.0);
+        assert!((a.normalize().abs() - 1.0).abs() < 1e-12);
+        assert_eq!(Vector2F::default().normalize(), Vector2F::default());
+        assert!((a.rotate(FRAC_PI_2) - b).abs() < 1e-12);
+        assert!((a.angle_to(b) - FRAC_PI_2).abs() < 1e-12);
+        assert_eq!(a.lerp(b, 0.5), Vector2F::new(-0.5, 3.5));
+        assert_eq!(Vector2F::new(0.0, 1.0).angle(), FRAC_PI_2);
+        assert_eq!(Vector2F::new(2.0, 5.0).distance_to_line(Vector2F::new(0.0, 0.0), Vector2F::new(10.0, 0.0)).abs(), 5.0);
+        assert_eq!(Vector2F::new(3.0, 4.0).distance_to_line(Vector2F::default(), Vector2F::default()), 5.0);
+        assert_eq!(Vector2F::new(2.6, -1.4).round(), Vector2D { x: 3, y: -1 });
+    }
+}
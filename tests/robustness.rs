@@ -0,0 +1,87 @@
+// Movement-RS - More or less accurate shape recognition
+// Copyright (C) 2025  Linus Tibert
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public Licence as published
+// by the Free Software Foundation, either version 3 of the Licence, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public Licence for more details.
+//
+// You should have received a copy of the GNU Affero General Public Licence
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Random strokes must never make the classification panic or report a score that is not a number,
+//! and the operators on pixel coordinates must saturate instead of overflowing.
+
+use std::time::Duration;
+
+use movement::glyph::builtin_templates;
+use movement::stroke_file::{format_stroke, parse_strokes};
+use movement::{Classification, Classifier, Hypothesis, Limits, Shape, ShapeName, Vector2D};
+use proptest::prelude::*;
+
+/// Coordinates near the origin, on a screen, and anywhere an `i32` can reach.
+fn coordinate() -> impl Strategy<Value = i32> {
+    prop_oneof![-5..5, 0..4000, any::<i32>()]
+}
+
+fn point() -> impl Strategy<Value = Vector2D> {
+    (coordinate(), coordinate()).prop_map(|(x, y)| Vector2D { x, y })
+}
+
+/// Strokes of up to 80 points, some of them repeating the same few points over and over.
+fn stroke() -> impl Strategy<Value = Vec<Vector2D>> {
+    prop_oneof![
+        prop::collection::vec(point(), 0..80),
+        (prop::collection::vec(point(), 1..4), 0..80usize).prop_map(|(points, count)| (0..count).map(|i| points[i % points.len()]).collect()),
+    ]
+}
+
+fn check(classification: &Classification) -> Result<(), TestCaseError> {
+    prop_assert!(classification.score.is_finite(), "{:?}", classification);
+    prop_assert!((0.0..=100.0).contains(&classification.score), "{:?}", classification);
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn classification_is_total(coordinates in stroke(), milliseconds in prop::option::of(0..100_000u64)) {
+        let shape: Shape = Shape { coordinates, shape_type: ShapeName::Undefined };
+        check(&shape.classify())?;
+        check(&Classifier::default().classify_timed(&shape, milliseconds.map(Duration::from_millis)))?;
+        let unlimited: Classifier = Classifier { limits: Limits { min_points: 0, min_size_px: 0.0, min_speed_px_per_s: 0.0, max_speed_px_per_s: f64::INFINITY }, ..Classifier::default() };
+        let classification: Classification = unlimited.classify(&shape);
+        check(&classification)?;
+        let hypotheses: Vec<Hypothesis> = unlimited.rank(&shape);
+        prop_assert!((hypotheses.iter().map(|hypothesis| hypothesis.probability).sum::<f64>() - 1.0).abs() < 1e-9);
+        let _ = shape.fit_primitive(&classification.candidate);
+        let _ = shape.segments();
+        let _ = shape.glyph(&builtin_templates(), 3);
+    }
+
+    #[test]
+    fn operators_saturate(a in point(), b in point(), factor in coordinate()) {
+        let clamp = |value: i128| value.clamp(i32::MIN as i128, i32::MAX as i128) as i32;
+        let (x, y) = ((a.x as i128, b.x as i128), (a.y as i128, b.y as i128));
+        prop_assert_eq!(a + b, Vector2D { x: clamp(x.0 + x.1), y: clamp(y.0 + y.1) });
+        prop_assert_eq!(a - b, Vector2D { x: clamp(x.0 - x.1), y: clamp(y.0 - y.1) });
+        prop_assert_eq!(a * factor, Vector2D { x: clamp(x.0 * factor as i128), y: clamp(y.0 * factor as i128) });
+        prop_assert_eq!(a * b, clamp(x.0 * x.1 + y.0 * y.1));
+        let extreme: Vector2D = Vector2D { x: i32::MIN, y: i32::MAX };
+        prop_assert_eq!((extreme + extreme, extreme - extreme * -1, extreme * extreme), (extreme, extreme, i32::MAX));
+    }
+
+    #[test]
+    fn stroke_files_round_trip(coordinates in prop::collection::vec(point(), 1..40)) {
+        prop_assert_eq!(parse_strokes(&format_stroke(&coordinates)).unwrap(), vec![coordinates]);
+    }
+
+    #[test]
+    fn parsing_is_total(text in "[0-9, \\-\n#]{0,200}") {
+        let _ = parse_strokes(&text);
+    }
+}